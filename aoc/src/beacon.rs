@@ -0,0 +1,121 @@
+//! Day 15 tooling: solving with another search area, row or multiplier than the puzzle's.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::Args;
+use common::Error;
+use day15::{BeaconSearch, Config, MapFormat, Tunnel};
+
+use crate::input::InputArgs;
+use crate::Result;
+
+#[derive(Debug, Args)]
+pub struct BeaconArgs {
+    #[command(flatten)]
+    source: InputArgs,
+
+    /// Start from the configuration of the example (bounds 20, row 10) instead of the puzzle's
+    #[arg(long)]
+    example: bool,
+
+    /// Largest x coordinate of the distress beacon
+    #[arg(long)]
+    x_bound: Option<i64>,
+
+    /// Largest y coordinate of the distress beacon
+    #[arg(long)]
+    y_bound: Option<i64>,
+
+    /// Row on which to count the positions without a beacon (part 1)
+    #[arg(long, allow_negative_numbers = true)]
+    row: Option<i64>,
+
+    /// Factor applied to x in the tuning frequency (part 2)
+    #[arg(long)]
+    multiplier: Option<i64>,
+
+    /// How to find the distress beacon: `edges` or `row-scan`
+    #[arg(long, default_value = "edges")]
+    search: BeaconSearch,
+
+    /// Draw the coverage of the sensors instead of solving: ascii, pgm or png
+    #[arg(long)]
+    map: Option<MapFormat>,
+
+    /// Area to draw, as `x_min,y_min,x_max,y_max` (defaults to the search bounds)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        allow_negative_numbers = true,
+        value_name = "X_MIN,Y_MIN,X_MAX,Y_MAX"
+    )]
+    window: Vec<i64>,
+
+    /// Largest side of the images, in pixels
+    #[arg(long, default_value_t = 1000)]
+    size: usize,
+
+    /// Write the map to this file instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+impl BeaconArgs {
+    fn config(&self) -> Config {
+        let preset = if self.example {
+            Config::example()
+        } else {
+            Config::default()
+        };
+        Config {
+            x_bound: self.x_bound.unwrap_or(preset.x_bound),
+            y_bound: self.y_bound.unwrap_or(preset.y_bound),
+            row: self.row.unwrap_or(preset.row),
+            multiplier: self.multiplier.unwrap_or(preset.multiplier),
+        }
+    }
+}
+
+pub fn run(args: BeaconArgs) -> Result<()> {
+    let config = args.config();
+    let tunnel = Tunnel::parse(&args.source.read(15)?, config)?;
+    if let Some(format) = args.map {
+        return draw(&tunnel, &config, format, &args);
+    }
+    println!(
+        "Positions without a beacon on row {}: {}",
+        config.row,
+        tunnel.number_of_not_beacons_on_line(config.row)
+    );
+    println!(
+        "Tuning frequency: {}",
+        tunnel.tuning_frequency_with(args.search)?
+    );
+    Ok(())
+}
+
+fn draw(tunnel: &Tunnel, config: &Config, format: MapFormat, args: &BeaconArgs) -> Result<()> {
+    let (xs, ys) = match args.window[..] {
+        [] => (0..=config.x_bound, 0..=config.y_bound),
+        [x_min, y_min, x_max, y_max] => (x_min..=x_max, y_min..=y_max),
+        _ => return Err("--window takes 4 values: x_min,y_min,x_max,y_max".into()),
+    };
+    if format == MapFormat::Png && args.output.is_none() {
+        return Err("a PNG map needs an --output file".into());
+    }
+    let map = tunnel
+        .render_map(format, xs, ys, args.size)
+        .map_err(|e| match e {
+            Error::InvalidArgument(_) if args.window.is_empty() => {
+                format!("{e}; pick a smaller area with --window").into()
+            }
+            e => Box::<dyn std::error::Error>::from(e),
+        })?;
+    match &args.output {
+        Some(path) => std::fs::write(path, map)
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?,
+        None => std::io::stdout().write_all(&map)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! Times the parsing and both parts of every day.
+//!
+//! Each day is run a few times to warm up, then each stage is sampled
+//! separately so a slow parser does not hide in the timings of the parts.
+
+use std::fmt::Display;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use common::Part;
+use serde::Serialize;
+
+use crate::{days, input, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part 1"),
+            Stage::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Timing {
+    pub day: u8,
+    pub stage: Stage,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "p95_ns", serialize_with = "as_nanos")]
+    pub p95: Duration,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub warmup: usize,
+    pub samples: usize,
+    pub timings: Vec<Timing>,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:<5}{:<8}{:>12}{:>12}", "Day", "Stage", "Median", "p95")?;
+        for timing in &self.timings {
+            writeln!(
+                f,
+                "{:<5}{:<8}{:>12}{:>12}",
+                format!("{:02}", timing.day),
+                timing.stage.to_string(),
+                format!("{:.1?}", timing.median),
+                format!("{:.1?}", timing.p95),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn as_nanos<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+pub fn bench(days: &[u8], inputs: &Path, warmup: usize, samples: usize) -> Result<Report> {
+    let mut timings = Vec::new();
+
+    for &day in days {
+        let path = input::path_in(inputs, day);
+        if !path.is_file() {
+            eprintln!("skipping day {day:02}: no input at {}", path.display());
+            continue;
+        }
+        let input = input::read_file(&path)?;
+
+        for _ in 0..warmup {
+            let solution = days::parse(day, &input)?;
+            for part in Part::ALL {
+                solution.answer(part)?;
+            }
+        }
+
+        let parse = sample(samples, || days::parse(day, &input).map(|_| ()))?;
+        timings.push(Timing::new(day, Stage::Parse, parse));
+
+        let solution = days::parse(day, &input)?;
+        for (part, stage) in [(Part::One, Stage::Part1), (Part::Two, Stage::Part2)] {
+            let durations = sample(samples, || solution.answer(part).map(|_| ()))?;
+            timings.push(Timing::new(day, stage, durations));
+        }
+    }
+
+    Ok(Report {
+        warmup,
+        samples,
+        timings,
+    })
+}
+
+fn sample(
+    samples: usize,
+    mut f: impl FnMut() -> common::Result<()>,
+) -> common::Result<Vec<Duration>> {
+    (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            f()?;
+            Ok(start.elapsed())
+        })
+        .collect()
+}
+
+impl Timing {
+    fn new(day: u8, stage: Stage, mut durations: Vec<Duration>) -> Timing {
+        durations.sort();
+        Timing {
+            day,
+            stage,
+            median: percentile(&durations, 50),
+            p95: percentile(&durations, 95),
+        }
+    }
+}
+
+/// Nearest-rank percentile of already sorted durations.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_nearest_rank() {
+        // GIVEN
+        let durations: Vec<_> = (1..=20).map(Duration::from_millis).collect();
+
+        // WHEN
+        let median = percentile(&durations, 50);
+        let p95 = percentile(&durations, 95);
+
+        // THEN
+        assert_eq!(Duration::from_millis(10), median);
+        assert_eq!(Duration::from_millis(19), p95);
+    }
+
+    #[test]
+    fn timing_sorts_samples() {
+        // GIVEN
+        let durations = [30, 10, 20].map(Duration::from_micros).to_vec();
+
+        // WHEN
+        let timing = Timing::new(4, Stage::Part2, durations);
+
+        // THEN
+        assert_eq!(Duration::from_micros(20), timing.median);
+        assert_eq!(Duration::from_micros(30), timing.p95);
+    }
+
+    #[test]
+    fn report_to_json() {
+        // GIVEN
+        let report = Report {
+            warmup: 1,
+            samples: 3,
+            timings: vec![Timing {
+                day: 1,
+                stage: Stage::Parse,
+                median: Duration::from_nanos(1500),
+                p95: Duration::from_nanos(2000),
+            }],
+        };
+
+        // WHEN
+        let json = serde_json::to_string(&report).unwrap();
+
+        // THEN
+        assert_eq!(
+            r#"{"warmup":1,"samples":3,"timings":[{"day":1,"stage":"parse","median_ns":1500,"p95_ns":2000}]}"#,
+            json
+        );
+    }
+}
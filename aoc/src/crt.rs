@@ -0,0 +1,85 @@
+//! Day 10 tooling: tracing the CRT and stopping the program at a given point.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use day10::debugger::Debugger;
+use day10::vm::Program;
+use day10::{ocr, Geometry, Renderer};
+
+use crate::input::InputArgs;
+use crate::Result;
+
+#[derive(Debug, Args)]
+pub struct CrtArgs {
+    #[command(flatten)]
+    source: InputArgs,
+
+    /// Print a row per cycle (instruction, X, sprite, pixel) before the picture
+    #[arg(long)]
+    trace: bool,
+
+    /// Stop once this cycle has been drawn
+    #[arg(long, conflicts_with = "break_on_x")]
+    until: Option<usize>,
+
+    /// Stop at the first cycle during which X holds this value (can be repeated)
+    #[arg(long = "break-on-x", allow_negative_numbers = true)]
+    break_on_x: Vec<i32>,
+
+    /// Width of the screen, in pixels
+    #[arg(long, default_value_t = 40)]
+    width: usize,
+
+    /// Height of the screen, in pixels
+    #[arg(long, default_value_t = 6)]
+    height: usize,
+
+    /// Width of the sprite, in pixels
+    #[arg(long, default_value_t = 3)]
+    sprite_width: usize,
+
+    /// How to draw the screen: text, blocks, ansi, pbm or pgm
+    #[arg(long, default_value = "text")]
+    render: Renderer,
+
+    /// Write the picture to this file instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: CrtArgs) -> Result<()> {
+    let program = Program::parse(&args.source.read(10)?)?;
+    let geometry = Geometry {
+        width: args.width,
+        height: args.height,
+        sprite_width: args.sprite_width,
+    };
+    let mut debugger = Debugger::with_geometry(program, geometry)?;
+
+    if !args.break_on_x.is_empty() {
+        for &x in &args.break_on_x {
+            debugger.break_on_x(x);
+        }
+        match debugger.resume()? {
+            Some(row) => println!("Breakpoint hit during cycle {} (X={})", row.cycle, row.x),
+            None => println!("No breakpoint hit"),
+        }
+    } else {
+        debugger.run_to_cycle(args.until.unwrap_or(usize::MAX))?;
+    }
+
+    if args.trace {
+        println!("{}", debugger.trace());
+    }
+    let picture = debugger.crt().render_with(args.render);
+    match args.output {
+        Some(output) => std::fs::write(&output, picture)
+            .map_err(|e| format!("cannot write {}: {e}", output.display()))?,
+        None => println!("{}", picture.trim_end()),
+    }
+    if let Ok(letters) = ocr::decode(&debugger.crt().render()) {
+        println!("{letters}");
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! Registry from a day number to the `Solution` of the matching crate.
+
+use common::{DynSolution, Error, Result, Solution};
+
+/// Last day with a solution.
+pub const LAST: u8 = 16;
+
+pub fn parse(day: u8, input: &str) -> Result<Box<dyn DynSolution>> {
+    match day {
+        1 => boxed::<day01::Inventory>(input),
+        2 => boxed::<day02::StrategyGuide>(input),
+        3 => boxed::<day03::Inventory>(input),
+        4 => boxed::<day04::WorkSheet>(input),
+        5 => boxed::<day05::Puzzle>(input),
+        6 => boxed::<day06::Datastream>(input),
+        7 => boxed::<day07::FileSystem>(input),
+        8 => boxed::<day08::Forest>(input),
+        9 => boxed::<day09::Motions>(input),
+        10 => boxed::<day10::Cpu>(input),
+        11 => boxed::<day11::Puzzle>(input),
+        12 => boxed::<day12::Area>(input),
+        13 => boxed::<day13::DistressSignal>(input),
+        14 => boxed::<day14::Cave>(input),
+        15 => boxed::<day15::Tunnel>(input),
+        16 => boxed::<day16::Cave>(input),
+        _ => Err(Error::NoSolution(format!("day {day} is not solved"))),
+    }
+}
+
+fn boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn DynSolution>> {
+    Ok(Box::new(S::parse(input)?))
+}
@@ -0,0 +1,156 @@
+//! Locating and reading the puzzle input of a day.
+//!
+//! The input is taken, in order, from an explicit `--input` file, from the
+//! conventional `<inputs>/dayNN.txt` file, or from stdin when something is piped
+//! into it.
+
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+
+use crate::Result;
+
+/// Where to look for the puzzle input of a day.
+#[derive(Debug, Clone, Args)]
+pub struct InputArgs {
+    /// File holding the puzzle input
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    /// Directory holding the puzzle inputs, as `dayNN.txt`
+    #[arg(long, default_value = "inputs")]
+    pub inputs: PathBuf,
+}
+
+impl InputArgs {
+    pub fn read(&self, day: u8) -> Result<String> {
+        resolve(day, self.input.as_deref(), &self.inputs)
+    }
+}
+
+pub fn path_in(inputs: &Path, day: u8) -> PathBuf {
+    inputs.join(format!("day{day:02}.txt"))
+}
+
+pub fn resolve(day: u8, input: Option<&Path>, inputs: &Path) -> Result<String> {
+    let stdin = std::io::stdin();
+    let piped = (!stdin.is_terminal()).then_some(stdin);
+    resolve_from(day, input, inputs, piped)
+}
+
+fn resolve_from(
+    day: u8,
+    input: Option<&Path>,
+    inputs: &Path,
+    stdin: Option<impl Read>,
+) -> Result<String> {
+    if let Some(path) = input {
+        return read_file(path);
+    }
+
+    let path = path_in(inputs, day);
+    if path.is_file() {
+        return read_file(&path);
+    }
+
+    if let Some(mut stdin) = stdin {
+        let mut text = String::new();
+        stdin.read_to_string(&mut text)?;
+        // Nothing was piped after all, as when stdin is /dev/null
+        if !text.is_empty() {
+            return Ok(normalise(&text));
+        }
+    }
+    Err(format!(
+        "no input for day {day:02}: pass --input, create {} or pipe it on stdin",
+        path.display()
+    )
+    .into())
+}
+
+pub fn read_file(path: &Path) -> Result<String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    Ok(normalise(&text))
+}
+
+/// Uses `\n` line endings, and makes sure a non-empty input ends with one.
+pub fn normalise(text: &str) -> String {
+    let mut text = text.replace("\r\n", "\n");
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalise_crlf_and_trailing_newline() {
+        // GIVEN
+        let inputs = ["noop\r\naddx 3\r\n", "noop\naddx 3", "noop\naddx 3\n", ""];
+
+        // WHEN
+        let normalised: Vec<_> = inputs.iter().map(|i| normalise(i)).collect();
+
+        // THEN
+        assert_eq!(
+            vec!["noop\naddx 3\n", "noop\naddx 3\n", "noop\naddx 3\n", ""],
+            normalised
+        );
+    }
+
+    #[test]
+    fn resolve_missing_explicit_file() {
+        // GIVEN
+        let path = Path::new("does/not/exist.txt");
+
+        // WHEN
+        let result = resolve(1, Some(path), Path::new("inputs"));
+
+        // THEN
+        assert!(result
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("cannot read does/not/exist.txt: "));
+    }
+
+    #[test]
+    fn resolve_conventional_file() {
+        // GIVEN
+        let inputs = std::env::temp_dir().join("aoc-resolve-conventional");
+        std::fs::create_dir_all(&inputs).unwrap();
+        std::fs::write(path_in(&inputs, 7), "$ cd /\r\n$ ls").unwrap();
+
+        // WHEN
+        let from_file = resolve_from(7, None, &inputs, None::<&[u8]>);
+        let before_stdin = resolve_from(7, None, &inputs, Some(&b"$ ls"[..]));
+        std::fs::remove_dir_all(&inputs).unwrap();
+
+        // THEN
+        assert_eq!("$ cd /\n$ ls\n", from_file.unwrap());
+        assert_eq!("$ cd /\n$ ls\n", before_stdin.unwrap());
+    }
+
+    #[test]
+    fn resolve_piped_stdin_without_conventional_file() {
+        // GIVEN
+        let inputs = std::env::temp_dir().join("aoc-resolve-piped");
+
+        // WHEN
+        let piped = resolve_from(7, None, &inputs, Some(&b"$ ls"[..]));
+        let empty = resolve_from(7, None, &inputs, Some(&b""[..]));
+
+        // THEN
+        assert_eq!("$ ls\n", piped.unwrap());
+        assert!(empty
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("no input for day 07: "));
+    }
+}
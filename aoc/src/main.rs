@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::{Parser, Subcommand};
+use common::Part;
+
+mod beacon;
+mod bench;
+mod crt;
+mod days;
+mod input;
+mod output;
+mod sand;
+mod valves;
+mod verify;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Single entry point for every day of the Advent of Code 2022
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day, reading its input from --input, from the inputs directory or from stdin
+    Run {
+        /// Day to solve
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=days::LAST as i64))]
+        day: u8,
+
+        #[command(flatten)]
+        source: input::InputArgs,
+
+        /// Only solve this part (1 or 2) instead of both
+        #[arg(long)]
+        part: Option<Part>,
+
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t)]
+        format: output::Format,
+    },
+    /// Check every solution against a file of recorded answers
+    Verify {
+        /// TOML file with the expected answers, as `[dayNN]` tables of `part1`/`part2` keys
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+
+        /// Directory holding the puzzle inputs, as `dayNN.txt`
+        #[arg(long, default_value = "inputs")]
+        inputs: PathBuf,
+    },
+    /// Time the parsing and both parts of every day
+    Bench {
+        /// Only benchmark this day
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=days::LAST as i64))]
+        day: Option<u8>,
+
+        /// Directory holding the puzzle inputs, as `dayNN.txt`
+        #[arg(long, default_value = "inputs")]
+        inputs: PathBuf,
+
+        /// Untimed runs of each day before sampling
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+
+        /// Timed runs of each stage
+        #[arg(long, default_value_t = 20)]
+        samples: usize,
+
+        /// Also write the timings to this JSON file
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Inspect the CRT of day 10, cycle by cycle
+    Crt(crt::CrtArgs),
+    /// Watch the sand of day 14 fall, or export the frames
+    Sand(sand::SandArgs),
+    /// Solve day 15 over another area, such as the example's
+    Beacon(beacon::BeaconArgs),
+    /// Compare the solvers of day 16, or explain the best plan
+    Valves(valves::ValvesArgs),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Run {
+            day,
+            source,
+            part,
+            format,
+        } => {
+            let input = source.read(day)?;
+
+            let solution = days::parse(day, &input)?;
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::ALL.to_vec(),
+            };
+            let mut records = Vec::new();
+            let mut failure = None;
+            for part in parts {
+                let start = Instant::now();
+                match solution.answer(part) {
+                    Ok(answer) => {
+                        records.push(output::Record::new(day, part, answer, start.elapsed()))
+                    }
+                    Err(e) => {
+                        failure = Some(format!("part {part}: {e}"));
+                        break;
+                    }
+                }
+            }
+            // Still show the answers found before the failing part
+            print!("{}", output::render(format, &records));
+            if let Some(failure) = failure {
+                return Err(failure.into());
+            }
+        }
+        Command::Verify { answers, inputs } => {
+            let text = std::fs::read_to_string(&answers)
+                .map_err(|e| format!("cannot read {}: {e}", answers.display()))?;
+            let expected = verify::load_answers(&text)?;
+            let outcomes = verify::verify(&expected, &inputs);
+
+            for outcome in &outcomes {
+                println!(
+                    "Day {:02} part {}: {}",
+                    outcome.day, outcome.part, outcome.status
+                );
+            }
+
+            let count =
+                |f: fn(&verify::Status) -> bool| outcomes.iter().filter(|o| f(&o.status)).count();
+            let passed = count(|s| matches!(s, verify::Status::Pass));
+            let regressed = count(|s| matches!(s, verify::Status::Regressed { .. }));
+            let failed = count(|s| matches!(s, verify::Status::Failed(_)));
+            let missing = count(|s| matches!(s, verify::Status::Missing));
+            println!("{passed} passed, {regressed} regressed, {failed} failed, {missing} missing");
+
+            let failures = count(verify::Status::is_failure);
+            if failures > 0 {
+                return Err(format!("{failures} answer(s) did not verify").into());
+            }
+        }
+        Command::Bench {
+            day,
+            inputs,
+            warmup,
+            samples,
+            output,
+        } => {
+            let days: Vec<u8> = match day {
+                Some(day) => vec![day],
+                None => (1..=days::LAST).collect(),
+            };
+            let report = bench::bench(&days, &inputs, warmup, samples)?;
+            print!("{report}");
+
+            if let Some(output) = output {
+                std::fs::write(&output, serde_json::to_string_pretty(&report)?)
+                    .map_err(|e| format!("cannot write {}: {e}", output.display()))?;
+            }
+        }
+        Command::Crt(args) => crt::run(args)?,
+        Command::Sand(args) => sand::run(args)?,
+        Command::Beacon(args) => beacon::run(args)?,
+        Command::Valves(args) => valves::run(args)?,
+    }
+
+    Ok(())
+}
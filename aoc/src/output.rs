@@ -0,0 +1,113 @@
+//! Rendering of answers, either for humans or for other tools.
+
+use std::time::Duration;
+
+use clap::ValueEnum;
+use common::{Answer, Part};
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One `Day NN part N: answer` line per part
+    #[default]
+    Text,
+    /// A JSON array with one object per part
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Value,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub elapsed_ns: u128,
+}
+
+impl Record {
+    pub fn new(day: u8, part: Part, answer: Answer, elapsed: Duration) -> Record {
+        let (answer, kind) = match answer {
+            Answer::Integer(n) => (Value::from(n), "integer"),
+            Answer::Unsigned(n) => (Value::from(n), "integer"),
+            Answer::Text(s) => (Value::from(s), "text"),
+        };
+        Record {
+            day,
+            part: part as u8,
+            answer,
+            kind,
+            elapsed_ns: elapsed.as_nanos(),
+        }
+    }
+}
+
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Text => records
+            .iter()
+            .map(|r| {
+                let answer = match &r.answer {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                format!("Day {:02} part {}: {answer}\n", r.day, r.part)
+            })
+            .collect(),
+        Format::Json => serde_json::to_string(records).unwrap() + "\n",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new(
+                10,
+                Part::One,
+                Answer::Integer(13140),
+                Duration::from_nanos(42),
+            ),
+            Record::new(
+                10,
+                Part::Two,
+                Answer::Text("##..\n..##".to_string()),
+                Duration::from_micros(1),
+            ),
+        ]
+    }
+
+    #[test]
+    fn render_text() {
+        // GIVEN
+        let records = records();
+
+        // WHEN
+        let output = render(Format::Text, &records);
+
+        // THEN
+        assert_eq!("Day 10 part 1: 13140\nDay 10 part 2: ##..\n..##\n", output);
+    }
+
+    #[test]
+    fn render_json() {
+        // GIVEN
+        let records = records();
+
+        // WHEN
+        let output = render(Format::Json, &records);
+
+        // THEN
+        assert_eq!(
+            concat!(
+                r#"[{"day":10,"part":1,"answer":13140,"type":"integer","elapsed_ns":42},"#,
+                r###"{"day":10,"part":2,"answer":"##..\n..##","type":"text","elapsed_ns":1000}]"###,
+                "\n"
+            ),
+            output
+        );
+    }
+}
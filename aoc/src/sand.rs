@@ -0,0 +1,118 @@
+//! Day 14 tooling: watching the sand fall in the terminal, or saving frames to files.
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use clap::Args;
+use day14::animation::Animation;
+use day14::{Cave, FloorSolver, Rules};
+
+use crate::input::{self, InputArgs};
+use crate::Result;
+
+#[derive(Debug, Args)]
+pub struct SandArgs {
+    #[command(flatten)]
+    source: InputArgs,
+
+    /// Let the sand land on the floor (part 2) instead of falling into the abyss
+    #[arg(long)]
+    floor: bool,
+
+    /// Simulation steps between two frames
+    #[arg(long, default_value_t = 1)]
+    every: usize,
+
+    /// Pause between two frames, in milliseconds
+    #[arg(long, default_value_t = 20)]
+    delay: u64,
+
+    /// Only show the final frame
+    #[arg(long = "final")]
+    final_only: bool,
+
+    /// Only show the frames after these numbers of steps (and the final one)
+    #[arg(long, value_delimiter = ',')]
+    frames: Vec<usize>,
+
+    /// Write the frames to text files in this directory instead of animating them
+    #[arg(long)]
+    export: Option<PathBuf>,
+
+    /// Start from a snapshot saved with `--save` instead of the puzzle input
+    #[arg(long, value_name = "SNAPSHOT")]
+    resume: Option<PathBuf>,
+
+    /// Write the final state of the cave to this file
+    #[arg(long, value_name = "SNAPSHOT")]
+    save: Option<PathBuf>,
+
+    /// Stop once this many grains came to rest
+    #[arg(long)]
+    max_grains: Option<usize>,
+
+    /// Only print how many grains rest on the floor, computed by `simulation` or `flood`
+    #[arg(long, value_name = "SOLVER")]
+    count: Option<FloorSolver>,
+}
+
+pub fn run(args: SandArgs) -> Result<()> {
+    let cave = match &args.resume {
+        Some(path) => Cave::from_snapshot(&input::read_file(path)?)?,
+        None => Cave::parse(&args.source.read(14)?)?,
+    };
+    if let Some(solver) = args.count {
+        println!("{}", cave.rests_on_floor(solver));
+        return Ok(());
+    }
+    let mut rules = if args.floor {
+        Rules::floor()
+    } else {
+        Rules::abyss()
+    };
+    if let Some(grains) = args.max_grains {
+        rules = rules.with_max_grains(grains);
+    }
+
+    let mut animation = if args.final_only {
+        Animation::at_steps(cave, rules, [])
+    } else if !args.frames.is_empty() {
+        Animation::at_steps(cave, rules, args.frames)
+    } else {
+        Animation::new(cave, rules, args.every)
+    };
+
+    if let Some(dir) = args.export {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+        let mut count = 0;
+        for frame in animation.by_ref() {
+            let path = dir.join(format!("frame_{:07}.txt", frame.step));
+            std::fs::write(&path, frame.picture)
+                .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+            count += 1;
+        }
+        println!("Wrote {count} frame(s) to {}", dir.display());
+        return save(args.save, animation.cave());
+    }
+
+    for frame in animation.by_ref() {
+        // Clear the screen and go back to its top left corner
+        print!("\x1b[2J\x1b[H{}", frame.picture);
+        println!(
+            "step {}, {} unit(s) of sand at rest",
+            frame.step, frame.rests
+        );
+        thread::sleep(Duration::from_millis(args.delay));
+    }
+    save(args.save, animation.cave())
+}
+
+fn save(path: Option<PathBuf>, cave: &Cave) -> Result<()> {
+    if let Some(path) = path {
+        std::fs::write(&path, cave.snapshot())
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Day 16 tooling: comparing the solvers and explaining the best plan.
+
+use clap::Args;
+use day16::{Cave, Scenario, Solver};
+
+use crate::input::InputArgs;
+use crate::Result;
+
+#[derive(Debug, Args)]
+pub struct ValvesArgs {
+    #[command(flatten)]
+    source: InputArgs,
+
+    /// Open the valves with the help of the elephant (part 2: 26 minutes, 2 actors)
+    #[arg(long)]
+    elephant: bool,
+
+    /// Valve where every actor starts
+    #[arg(long)]
+    start: Option<String>,
+
+    /// Minutes before the volcano erupts
+    #[arg(long)]
+    minutes: Option<usize>,
+
+    /// Number of actors opening valves together
+    #[arg(long)]
+    actors: Option<usize>,
+
+    /// How to search: `bitmask` or `branch-and-bound`
+    #[arg(long, default_value = "bitmask")]
+    solver: Solver,
+
+    /// Print the cave as a Graphviz graph instead of solving
+    #[arg(long)]
+    dot: bool,
+
+    /// With --dot, only keep the start and the valves worth opening, linked by their distances
+    #[arg(long, requires = "dot")]
+    compressed: bool,
+
+    /// Print the valves each actor opens and the pressure released minute by minute
+    #[arg(long)]
+    plan: bool,
+}
+
+impl ValvesArgs {
+    fn scenario(&self) -> Scenario {
+        let preset = if self.elephant {
+            Scenario::with_elephant()
+        } else {
+            Scenario::alone()
+        };
+        Scenario {
+            start: self.start.clone().unwrap_or(preset.start),
+            minutes: self.minutes.unwrap_or(preset.minutes),
+            actors: self.actors.unwrap_or(preset.actors),
+        }
+    }
+}
+
+pub fn run(args: ValvesArgs) -> Result<()> {
+    let cave = Cave::parse(&args.source.read(16)?)?;
+    let scenario = args.scenario();
+    if args.dot && args.compressed {
+        print!("{}", cave.to_compressed_dot(&scenario.start)?);
+    } else if args.dot {
+        print!("{}", cave.to_dot());
+    } else if args.plan {
+        print!("{}", cave.plan_for(&scenario)?);
+    } else {
+        println!("{}", cave.best_pressure(&scenario, args.solver)?);
+    }
+    Ok(())
+}
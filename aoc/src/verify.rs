@@ -0,0 +1,237 @@
+//! Checks every solution against a file of recorded answers.
+//!
+//! The answers file is TOML, with one table per day and one key per part:
+//!
+//! ```toml
+//! [day01]
+//! part1 = 24000
+//! part2 = 45000
+//!
+//! [day10]
+//! part2 = "PHELRPHE"
+//! ```
+//!
+//! The input of each listed day is read from `<inputs>/dayNN.txt`.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+
+use common::{Answer, Part};
+
+use crate::{days, input, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Regressed { expected: String, actual: String },
+    Failed(String),
+    Missing,
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Regressed { .. } | Status::Failed(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Regressed { expected, actual } => {
+                write!(f, "regressed, expected `{expected}`, got `{actual}`")
+            }
+            Status::Failed(reason) => write!(f, "fail, {reason}"),
+            Status::Missing => write!(f, "missing input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: Part,
+    pub status: Status,
+}
+
+pub fn load_answers(text: &str) -> Result<Vec<Expected>> {
+    let table: toml::value::Table = toml::from_str(text)?;
+    let mut answers = Vec::new();
+
+    for (name, parts) in table {
+        let day = name
+            .strip_prefix("day")
+            .and_then(|n| n.parse::<u8>().ok())
+            .ok_or_else(|| format!("invalid table `{name}`, expected `dayNN`"))?;
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("`{name}` should be a table"))?;
+
+        for (key, value) in parts {
+            let part = match key.as_str() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => {
+                    return Err(
+                        format!("invalid key `{name}.{key}`, expected `part1` or `part2`").into(),
+                    )
+                }
+            };
+            let answer = match value {
+                toml::Value::Integer(n) => n.to_string(),
+                toml::Value::String(s) => s.clone(),
+                _ => return Err(format!("`{name}.{key}` should be an integer or a string").into()),
+            };
+            answers.push(Expected { day, part, answer });
+        }
+    }
+
+    answers.sort_by_key(|e| (e.day, e.part));
+    Ok(answers)
+}
+
+pub fn verify(answers: &[Expected], inputs: &Path) -> Vec<Outcome> {
+    let mut by_day: BTreeMap<u8, Vec<&Expected>> = BTreeMap::new();
+    for expected in answers {
+        by_day.entry(expected.day).or_default().push(expected);
+    }
+
+    let mut outcomes = Vec::new();
+    for (day, expected) in by_day {
+        let path = input::path_in(inputs, day);
+        if !path.is_file() {
+            outcomes.extend(expected.iter().map(|e| Outcome {
+                day,
+                part: e.part,
+                status: Status::Missing,
+            }));
+            continue;
+        }
+        let solution = input::read_file(&path)
+            .and_then(|input| Ok(days::parse(day, &input)?))
+            .map_err(|e| e.to_string());
+
+        for e in expected {
+            let status = match &solution {
+                Ok(solution) => check(&e.answer, solution.answer(e.part)),
+                Err(reason) => Status::Failed(reason.clone()),
+            };
+            outcomes.push(Outcome {
+                day,
+                part: e.part,
+                status,
+            });
+        }
+    }
+    outcomes
+}
+
+fn check(expected: &str, actual: common::Result<Answer>) -> Status {
+    match actual {
+        Ok(actual) => {
+            let actual = actual.to_string();
+            if actual.trim_end() == expected.trim_end() {
+                Status::Pass
+            } else {
+                Status::Regressed {
+                    expected: expected.trim_end().to_string(),
+                    actual: actual.trim_end().to_string(),
+                }
+            }
+        }
+        Err(e) => Status::Failed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Error;
+    use indoc::indoc;
+
+    #[test]
+    fn load_answers_integers_and_text() {
+        // GIVEN
+        let text = indoc! {r#"
+            [day05]
+            part1 = "CMZ"
+
+            [day01]
+            part2 = 45000
+            part1 = 24000
+            "#};
+
+        // WHEN
+        let answers = load_answers(text).unwrap();
+
+        // THEN
+        let expected = vec![
+            Expected {
+                day: 1,
+                part: Part::One,
+                answer: "24000".to_string(),
+            },
+            Expected {
+                day: 1,
+                part: Part::Two,
+                answer: "45000".to_string(),
+            },
+            Expected {
+                day: 5,
+                part: Part::One,
+                answer: "CMZ".to_string(),
+            },
+        ];
+        assert_eq!(expected, answers);
+    }
+
+    #[test]
+    fn load_answers_invalid_part() {
+        // GIVEN
+        let text = indoc! {"
+            [day01]
+            part3 = 12
+            "};
+
+        // WHEN
+        let result = load_answers(text);
+
+        // THEN
+        assert_eq!(
+            "invalid key `day01.part3`, expected `part1` or `part2`",
+            result.err().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn check_statuses() {
+        // GIVEN
+        let expected = "##..\n..##\n";
+
+        // WHEN
+        let pass = check(expected, Ok(Answer::Text("##..\n..##".to_string())));
+        let regressed = check(expected, Ok(Answer::Text("##..\n..#.".to_string())));
+        let failed = check(expected, Err(Error::NoSolution("nope".to_string())));
+
+        // THEN
+        assert_eq!(Status::Pass, pass);
+        assert_eq!(
+            Status::Regressed {
+                expected: "##..\n..##".to_string(),
+                actual: "##..\n..#.".to_string()
+            },
+            regressed
+        );
+        assert_eq!(Status::Failed("no solution: nope".to_string()), failed);
+        assert!(regressed.is_failure());
+        assert!(!Status::Missing.is_failure());
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    /// An unsigned answer too large for an `i64`
+    Unsigned(u64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value.into())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(n) => Answer::Integer(n),
+                        Err(_) => Answer::Unsigned(value as u64),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i32, i64);
+impl_from_unsigned!(u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_from_integer() {
+        // GIVEN
+        let value: usize = 42;
+
+        // WHEN
+        let answer = Answer::from(value);
+
+        // THEN
+        assert_eq!(Answer::Integer(42), answer);
+        assert_eq!("42", answer.to_string());
+    }
+
+    #[test]
+    fn answer_from_large_unsigned() {
+        // GIVEN
+        let value = u64::MAX;
+
+        // WHEN
+        let answer = Answer::from(value);
+
+        // THEN
+        assert_eq!(Answer::Unsigned(u64::MAX), answer);
+        assert_eq!("18446744073709551615", answer.to_string());
+    }
+
+    #[test]
+    fn answer_from_string() {
+        // GIVEN
+        let value = "CMZ".to_string();
+
+        // WHEN
+        let answer = Answer::from(value);
+
+        // THEN
+        assert_eq!(Answer::Text("CMZ".to_string()), answer);
+        assert_eq!("CMZ", answer.to_string());
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt::Display;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The puzzle input does not have the expected shape
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        expected: String,
+    },
+    /// The puzzle input was understood, but no answer could be derived from it
+    NoSolution(String),
+    /// An option given alongside the puzzle input does not make sense for it
+    InvalidArgument(String),
+    /// The answer or a rendering of it could not be written out
+    Output(String),
+}
+
+impl Error {
+    /// Build a parse error pointing at `fragment`, which must be a slice of `input`.
+    pub fn parse(input: &str, fragment: &str, expected: impl Into<String>) -> Error {
+        let (line, column) = locate(input, fragment);
+        Error::Parse {
+            line,
+            column,
+            text: fragment.lines().next().unwrap_or_default().to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Translate an error located relative to `inner` into one located relative to `outer`,
+    /// `inner` being a slice of `outer` (typically one of its lines).
+    pub fn within(self, outer: &str, inner: &str) -> Error {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                text,
+                expected,
+            } => {
+                let (inner_line, inner_column) = locate(outer, inner);
+                Error::Parse {
+                    line: inner_line + line - 1,
+                    column: if line == 1 {
+                        inner_column + column - 1
+                    } else {
+                        column
+                    },
+                    text,
+                    expected,
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+fn locate(input: &str, fragment: &str) -> (usize, usize) {
+    let offset = (fragment.as_ptr() as usize)
+        .saturating_sub(input.as_ptr() as usize)
+        .min(input.len());
+    let before = input.get(..offset).unwrap_or(input);
+
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                text,
+                expected,
+            } => {
+                write!(f, "line {line}, column {column}: expected {expected}, ")?;
+                if text.is_empty() {
+                    write!(f, "found end of line")
+                } else {
+                    write!(f, "found `{text}`")
+                }
+            }
+            Error::NoSolution(reason) => write!(f, "no solution: {reason}"),
+            Error::InvalidArgument(reason) => write!(f, "invalid argument: {reason}"),
+            Error::Output(reason) => write!(f, "cannot write output: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_location() {
+        // GIVEN
+        let input = "1 2\n3 x4\n";
+        let fragment = &input[6..8];
+
+        // WHEN
+        let error = Error::parse(input, fragment, "a number");
+
+        // THEN
+        assert_eq!(
+            Error::Parse {
+                line: 2,
+                column: 3,
+                text: "x4".to_string(),
+                expected: "a number".to_string(),
+            },
+            error
+        );
+    }
+
+    #[test]
+    fn parse_error_within_line() {
+        // GIVEN
+        let input = "move 1 from 2 to 3\nmove 1 from x to 3\n";
+        let line = input.lines().nth(1).unwrap();
+        let error = Error::parse(line, &line[12..13], "a stack number");
+
+        // WHEN
+        let error = error.within(input, line);
+
+        // THEN
+        assert_eq!(
+            "line 2, column 13: expected a stack number, found `x`",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_error_at_end_of_input() {
+        // GIVEN
+        let input = "abc";
+
+        // WHEN
+        let error = Error::parse(input, &input[3..], "a digit");
+
+        // THEN
+        assert_eq!(
+            "line 1, column 4: expected a digit, found end of line",
+            error.to_string()
+        );
+    }
+}
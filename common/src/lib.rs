@@ -0,0 +1,9 @@
+mod answer;
+mod error;
+#[cfg(feature = "nom")]
+pub mod nom;
+mod solution;
+
+pub use answer::Answer;
+pub use error::{Error, Result};
+pub use solution::{DynSolution, Part, Solution};
@@ -0,0 +1,63 @@
+use nom::IResult;
+
+use crate::{Error, Result};
+
+/// Turn the outcome of a top-level `nom` parser into a located [`Error`], rejecting any
+/// unconsumed input other than trailing whitespace.
+pub fn finish<'a, T>(input: &'a str, result: IResult<&'a str, T>, expected: &str) -> Result<T> {
+    match result {
+        Ok((remainder, _)) if !remainder.trim().is_empty() => {
+            Err(Error::parse(input, remainder.trim_start(), expected))
+        }
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            Err(Error::parse(input, e.input, expected))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(Error::parse(input, &input[input.len()..], expected)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, character::complete::digit1, multi::separated_list1};
+
+    fn numbers(input: &str) -> IResult<&str, Vec<&str>> {
+        separated_list1(tag(","), digit1)(input)
+    }
+
+    #[test]
+    fn finish_trailing_input() {
+        // GIVEN
+        let input = "1,2,3\n4;5\n";
+
+        // WHEN
+        let result = finish(input, numbers(input), "comma-separated numbers");
+
+        // THEN
+        assert_eq!(
+            Err(Error::Parse {
+                line: 2,
+                column: 1,
+                text: "4;5".to_string(),
+                expected: "comma-separated numbers".to_string(),
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn finish_parser_error() {
+        // GIVEN
+        let input = "a,2";
+
+        // WHEN
+        let result = finish(input, numbers(input), "comma-separated numbers");
+
+        // THEN
+        assert_eq!(
+            "line 1, column 1: expected comma-separated numbers, found `a,2`",
+            result.unwrap_err().to_string()
+        );
+    }
+}
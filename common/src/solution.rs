@@ -0,0 +1,109 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Answer, Result};
+
+/// A day of the Advent of Code: the input is parsed once, then both parts are answered from it.
+pub trait Solution: Sized {
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self>;
+
+    fn part1(&self) -> Result<Self::Part1>;
+
+    fn part2(&self) -> Result<Self::Part2>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", *self as u8)
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part {s}, expected 1 or 2")),
+        }
+    }
+}
+
+/// Object-safe view of a [`Solution`], so that every day can be handled through the same type.
+pub trait DynSolution {
+    fn answer(&self, part: Part) -> Result<Answer>;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn answer(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => self.part1().map(Into::into),
+            Part::Two => self.part2().map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lengths(Vec<usize>);
+
+    impl Solution for Lengths {
+        type Part1 = usize;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Self> {
+            Ok(Lengths(input.lines().map(str::len).collect()))
+        }
+
+        fn part1(&self) -> Result<Self::Part1> {
+            Ok(self.0.iter().sum())
+        }
+
+        fn part2(&self) -> Result<Self::Part2> {
+            Ok(format!("{:?}", self.0))
+        }
+    }
+
+    #[test]
+    fn part_from_str() {
+        // GIVEN
+        let inputs = ["1", "2", "3"];
+
+        // WHEN
+        let parts: Vec<_> = inputs.iter().map(|s| s.parse::<Part>()).collect();
+
+        // THEN
+        assert_eq!(Ok(Part::One), parts[0]);
+        assert_eq!(Ok(Part::Two), parts[1]);
+        assert!(parts[2].is_err());
+    }
+
+    #[test]
+    fn dyn_solution_answer() {
+        // GIVEN
+        let solution: Box<dyn DynSolution> = Box::new(Lengths::parse("ab\ncde\n").unwrap());
+
+        // WHEN
+        let part1 = solution.answer(Part::One);
+        let part2 = solution.answer(Part::Two);
+
+        // THEN
+        assert_eq!(Ok(Answer::Integer(5)), part1);
+        assert_eq!(Ok(Answer::Text("[2, 3]".to_string())), part2);
+    }
+}
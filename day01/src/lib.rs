@@ -1,27 +1,34 @@
+use common::{Error, Result, Solution};
+
 type Elf = Vec<u32>;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Inventory(Vec<Elf>);
 
 impl Inventory {
-    pub fn from_string(string: &str) -> Inventory {
-        Inventory(
+    pub fn from_string(string: &str) -> Result<Inventory> {
+        Ok(Inventory(
             string
                 .trim()
                 .split("\n\n")
                 .map(|single_elf| {
                     single_elf
                         .split('\n')
-                        .map(|item| item.parse().unwrap())
+                        .map(|item| {
+                            item.parse()
+                                .map_err(|_| Error::parse(string, item, "a number of calories"))
+                        })
                         .collect()
                 })
-                .collect(),
-        )
+                .collect::<Result<_>>()?,
+        ))
     }
 
-    pub fn highest_sum(&self) -> u32 {
+    pub fn highest_sum(&self) -> Result<u32> {
         let calories = self.0.iter().map(|v| v.iter().sum());
-        calories.max().unwrap()
+        calories
+            .max()
+            .ok_or_else(|| Error::NoSolution("the inventory is empty".to_string()))
     }
 
     pub fn highest_three_sum(&self) -> u32 {
@@ -39,6 +46,23 @@ impl Inventory {
     }
 }
 
+impl Solution for Inventory {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self> {
+        Inventory::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        self.highest_sum()
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        Ok(self.highest_three_sum())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,7 +78,7 @@ mod tests {
         let result = Inventory::from_string(string);
 
         // THEN
-        assert_eq!(expected_inventory, result);
+        assert_eq!(Ok(expected_inventory), result);
     }
 
     #[test]
@@ -68,7 +92,7 @@ mod tests {
         let result = Inventory::from_string(string);
 
         // THEN
-        assert_eq!(expected_inventory, result);
+        assert_eq!(Ok(expected_inventory), result);
     }
 
     #[test]
@@ -82,7 +106,27 @@ mod tests {
         let result = Inventory::from_string(string);
 
         // THEN
-        assert_eq!(expected_inventory, result);
+        assert_eq!(Ok(expected_inventory), result);
+    }
+
+    #[test]
+    fn construct_inventory_invalid_item() {
+        // GIVEN
+        let string = "1\n2\n\n3x\n";
+
+        // WHEN
+        let result = Inventory::from_string(string);
+
+        // THEN
+        assert_eq!(
+            Err(Error::Parse {
+                line: 4,
+                column: 1,
+                text: "3x".to_string(),
+                expected: "a number of calories".to_string(),
+            }),
+            result
+        );
     }
 
     #[test]
@@ -94,7 +138,7 @@ mod tests {
         let result = inventory.highest_sum();
 
         // THEN
-        assert_eq!(42, result);
+        assert_eq!(Ok(42), result);
     }
 
     #[test]
@@ -106,7 +150,7 @@ mod tests {
         let result = inventory.highest_sum();
 
         // THEN
-        assert_eq!(51, result);
+        assert_eq!(Ok(51), result);
     }
 
     #[test]
@@ -118,7 +162,7 @@ mod tests {
         let result = inventory.highest_sum();
 
         // THEN
-        assert_eq!(24, result);
+        assert_eq!(Ok(24), result);
     }
 
     #[test]
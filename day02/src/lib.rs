@@ -0,0 +1,29 @@
+use common::{Result, Solution};
+
+pub mod part1;
+pub mod part2;
+
+pub struct StrategyGuide {
+    moves: part1::Strategy,
+    outcomes: part2::Strategy,
+}
+
+impl Solution for StrategyGuide {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(StrategyGuide {
+            moves: part1::Strategy::from_string(input)?,
+            outcomes: part2::Strategy::from_string(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        Ok(self.moves.predict_score())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        Ok(self.outcomes.predict_score())
+    }
+}
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use common::{Error, Result};
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum Move {
     Rock = 1,
@@ -9,14 +11,14 @@ enum Move {
 use Move::*;
 
 impl FromStr for Move {
-    type Err = &'static str;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         match s {
             "A" | "X" => Ok(Move::Rock),
             "B" | "Y" => Ok(Move::Paper),
             "C" | "Z" => Ok(Move::Scissors),
-            _ => Err("Cannot parse Move: invalid char"),
+            _ => Err(Error::parse(s, s, "a move (A, B, C, X, Y or Z)")),
         }
     }
 }
@@ -28,17 +30,16 @@ struct Round {
 }
 
 impl Round {
-    fn from_line(line: &str) -> Result<Self, &'static str> {
+    fn from_line(line: &str) -> Result<Self> {
         let (opponent, your) = line
             .split_once(' ')
-            .ok_or("Cannot create Round: unable to split")?;
-        match (opponent.parse(), your.parse()) {
-            (Ok(opponent_move), Ok(your_move)) => Ok(Round {
-                opponent_move,
-                your_move,
-            }),
-            _ => Err("Cannot create Round: Move parse failed"),
-        }
+            .ok_or_else(|| Error::parse(line, line, "two space-separated letters"))?;
+        Ok(Round {
+            opponent_move: opponent
+                .parse()
+                .map_err(|e: Error| e.within(line, opponent))?,
+            your_move: your.parse().map_err(|e: Error| e.within(line, your))?,
+        })
     }
 
     fn score(&self) -> u32 {
@@ -67,13 +68,13 @@ impl Round {
 pub struct Strategy(Vec<Round>);
 
 impl Strategy {
-    pub fn from_string(input: &str) -> Strategy {
-        Strategy(
+    pub fn from_string(input: &str) -> Result<Strategy> {
+        Ok(Strategy(
             input
                 .lines()
-                .filter_map(|line| Round::from_line(line).ok())
-                .collect(),
-        )
+                .map(|line| Round::from_line(line).map_err(|e| e.within(input, line)))
+                .collect::<Result<_>>()?,
+        ))
     }
 
     pub fn predict_score(&self) -> u32 {
@@ -91,7 +92,7 @@ mod tests {
         let input = "";
 
         // WHEN
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // THEN
         let expected_rounds: Vec<Round> = vec![];
@@ -104,7 +105,7 @@ mod tests {
         let input = "A Y\nB X\nC Z\n";
 
         // WHEN
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // THEN
         let expected_rounds = vec![
@@ -124,11 +125,26 @@ mod tests {
         assert_eq!(expected_rounds, strategy.0);
     }
 
+    #[test]
+    fn strategy_from_string_invalid_letter() {
+        // GIVEN
+        let input = "A Y\nB W\n";
+
+        // WHEN
+        let result = Strategy::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 2, column 3: expected a move (A, B, C, X, Y or Z), found `W`",
+            result.err().unwrap().to_string()
+        );
+    }
+
     #[test]
     fn predict_score_rock_vs_paper() {
         // GIVEN
         let input = "A Y\n";
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // WHEN
         let score = strategy.predict_score();
@@ -141,7 +157,7 @@ mod tests {
     fn predict_score_paper_vs_rock() {
         // GIVEN
         let input = "B X\n";
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // WHEN
         let score = strategy.predict_score();
@@ -154,7 +170,7 @@ mod tests {
     fn predict_score_three_rounds() {
         // GIVEN
         let input = "A Y\nB X\nC Z\n";
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // WHEN
         let score = strategy.predict_score();
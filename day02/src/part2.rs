@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use common::{Error, Result};
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum Move {
     Rock = 1,
@@ -9,14 +11,14 @@ enum Move {
 use Move::*;
 
 impl FromStr for Move {
-    type Err = &'static str;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         match s {
             "A" => Ok(Rock),
             "B" => Ok(Paper),
             "C" => Ok(Scissors),
-            _ => Err("Cannot parse Move: invalid char"),
+            _ => Err(Error::parse(s, s, "a move (A, B or C)")),
         }
     }
 }
@@ -30,14 +32,14 @@ enum Outcome {
 use Outcome::*;
 
 impl FromStr for Outcome {
-    type Err = &'static str;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         match s {
             "X" => Ok(Loss),
             "Y" => Ok(Draw),
             "Z" => Ok(Win),
-            _ => Err("Cannot parse Outcome: invalid char"),
+            _ => Err(Error::parse(s, s, "an outcome (X, Y or Z)")),
         }
     }
 }
@@ -49,17 +51,18 @@ struct Round {
 }
 
 impl Round {
-    fn from_line(line: &str) -> Result<Self, &'static str> {
+    fn from_line(line: &str) -> Result<Self> {
         let (opponent, outcome) = line
             .split_once(' ')
-            .ok_or("Cannot create Round: unable to split")?;
-        match (opponent.parse(), outcome.parse()) {
-            (Ok(opponent_move), Ok(desired_outcome)) => Ok(Round {
-                opponent_move,
-                desired_outcome,
-            }),
-            _ => Err("Cannot create Round: Move parse failed"),
-        }
+            .ok_or_else(|| Error::parse(line, line, "two space-separated letters"))?;
+        Ok(Round {
+            opponent_move: opponent
+                .parse()
+                .map_err(|e: Error| e.within(line, opponent))?,
+            desired_outcome: outcome
+                .parse()
+                .map_err(|e: Error| e.within(line, outcome))?,
+        })
     }
 
     fn score(&self) -> u32 {
@@ -89,13 +92,13 @@ impl Round {
 pub struct Strategy(Vec<Round>);
 
 impl Strategy {
-    pub fn from_string(input: &str) -> Strategy {
-        Strategy(
+    pub fn from_string(input: &str) -> Result<Strategy> {
+        Ok(Strategy(
             input
                 .lines()
-                .filter_map(|line| Round::from_line(line).ok())
-                .collect(),
-        )
+                .map(|line| Round::from_line(line).map_err(|e| e.within(input, line)))
+                .collect::<Result<_>>()?,
+        ))
     }
 
     pub fn predict_score(&self) -> u32 {
@@ -113,7 +116,7 @@ mod tests {
         let input = "";
 
         // WHEN
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // THEN
         let expected_rounds: Vec<Round> = vec![];
@@ -126,7 +129,7 @@ mod tests {
         let input = "A Y\nB X\nC Z\n";
 
         // WHEN
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // THEN
         let expected_rounds = vec![
@@ -146,11 +149,26 @@ mod tests {
         assert_eq!(expected_rounds, strategy.0);
     }
 
+    #[test]
+    fn strategy_from_string_invalid_letter() {
+        // GIVEN
+        let input = "A Y\nB W\n";
+
+        // WHEN
+        let result = Strategy::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 2, column 3: expected an outcome (X, Y or Z), found `W`",
+            result.err().unwrap().to_string()
+        );
+    }
+
     #[test]
     fn predict_score_draw_vs_paper() {
         // GIVEN
         let input = "A Y\n";
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // WHEN
         let score = strategy.predict_score();
@@ -163,7 +181,7 @@ mod tests {
     fn predict_score_lose_vs_rock() {
         // GIVEN
         let input = "B X\n";
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // WHEN
         let score = strategy.predict_score();
@@ -176,7 +194,7 @@ mod tests {
     fn predict_score_three_rounds() {
         // GIVEN
         let input = "A Y\nB X\nC Z\n";
-        let strategy = Strategy::from_string(input);
+        let strategy = Strategy::from_string(input).unwrap();
 
         // WHEN
         let score = strategy.predict_score();
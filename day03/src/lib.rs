@@ -1,4 +1,4 @@
-#![feature(iter_array_chunks)]
+use common::{Error, Result, Solution};
 use std::collections::HashSet;
 
 struct Rucksack {
@@ -7,29 +7,40 @@ struct Rucksack {
 }
 
 impl Rucksack {
-    fn from_line(line: &str) -> Self {
+    fn from_line(line: &str) -> Result<Self> {
+        if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            let item = &line[idx..idx + c.len_utf8()];
+            return Err(Error::parse(line, item, "an item letter"));
+        }
+        if !line.len().is_multiple_of(2) {
+            return Err(Error::parse(line, line, "an even number of items"));
+        }
+
         let chars = line.as_bytes();
         let len = chars.len();
         let (left, right) = chars.split_at(len / 2);
-        Rucksack {
+        Ok(Rucksack {
             left: left.iter().copied().collect(),
             right: right.iter().copied().collect(),
-        }
+        })
     }
 
-    fn common_item(&self) -> Result<u8, &'static str> {
+    fn common_item(&self) -> Result<u8> {
         self.left
             .intersection(&self.right)
             .next()
             .copied()
-            .ok_or("No common item")
+            .ok_or_else(|| Error::NoSolution("no common item in rucksack".to_string()))
     }
 
-    fn priority(item: u8) -> Result<u32, &'static str> {
+    fn priority(item: u8) -> Result<u32> {
         match item {
             b'a'..=b'z' => Ok((item - b'a' + 1).into()),
             b'A'..=b'Z' => Ok((item - b'A' + 27).into()),
-            _ => Err("item is not a letter"),
+            _ => Err(Error::NoSolution(format!(
+                "item {} is not a letter",
+                item as char
+            ))),
         }
     }
 }
@@ -37,31 +48,42 @@ impl Rucksack {
 struct Group([Rucksack; 3]);
 
 impl Group {
-    fn find_badge(&self) -> Result<u8, &'static str> {
+    fn find_badge(&self) -> Result<u8> {
         self.0
             .iter()
             .map(|rucksack| rucksack.left.union(&rucksack.right).copied().collect())
             .reduce(|acc: HashSet<u8>, rucksack| acc.intersection(&rucksack).copied().collect())
-            .ok_or("Cannot find badge: empty group")
-            .and_then(|set| set.iter().next().copied().ok_or("No badge found"))
+            .and_then(|set| set.iter().next().copied())
+            .ok_or_else(|| Error::NoSolution("no badge found in group".to_string()))
     }
 }
 
 pub struct Inventory(Vec<Group>);
 
 impl Inventory {
-    pub fn from_string(input: &str) -> Self {
-        Inventory(
-            input
-                .lines()
-                .map(Rucksack::from_line)
-                .array_chunks()
-                .map(Group)
-                .collect(),
-        )
-    }
-
-    pub fn priority(&self) -> Result<u32, &'static str> {
+    pub fn from_string(input: &str) -> Result<Self> {
+        let rucksacks = input
+            .lines()
+            .map(|line| Rucksack::from_line(line).map_err(|e| e.within(input, line)))
+            .collect::<Result<Vec<_>>>()?;
+        if rucksacks.len() % 3 != 0 {
+            let last_line = input.lines().last().unwrap_or_default();
+            return Err(Error::parse(input, last_line, "groups of three rucksacks"));
+        }
+
+        // Poor man's array_chunks
+        let mut rucksacks = rucksacks.into_iter();
+        let groups = std::iter::from_fn(|| {
+            Some(Group([
+                rucksacks.next()?,
+                rucksacks.next()?,
+                rucksacks.next()?,
+            ]))
+        });
+        Ok(Inventory(groups.collect()))
+    }
+
+    pub fn priority(&self) -> Result<u32> {
         self.0
             .iter()
             .map(|group| {
@@ -69,12 +91,12 @@ impl Inventory {
                     .0
                     .iter()
                     .map(|rucksack| rucksack.common_item().and_then(Rucksack::priority))
-                    .sum::<Result<u32, &'static str>>()
+                    .sum::<Result<u32>>()
             })
             .sum()
     }
 
-    pub fn badge_priority(&self) -> Result<u32, &'static str> {
+    pub fn badge_priority(&self) -> Result<u32> {
         self.0
             .iter()
             .map(|group| group.find_badge().and_then(Rucksack::priority))
@@ -82,6 +104,23 @@ impl Inventory {
     }
 }
 
+impl Solution for Inventory {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self> {
+        Inventory::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        self.priority()
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        self.badge_priority()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,7 +131,7 @@ mod tests {
         let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
 
         // WHEN
-        let rucksack = Rucksack::from_line(line);
+        let rucksack = Rucksack::from_line(line).unwrap();
 
         // THEN
         assert_eq!(
@@ -105,11 +144,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rucksack_from_line_invalid_item() {
+        // GIVEN
+        let line = "vJrwpWtw-gWrhcsFMMfFFhFp";
+
+        // WHEN
+        let result = Rucksack::from_line(line);
+
+        // THEN
+        assert_eq!(
+            "line 1, column 9: expected an item letter, found `-`",
+            result.err().unwrap().to_string()
+        );
+    }
+
     #[test]
     fn rucksack_common_item() {
         // GIVEN
         let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
-        let rucksack = Rucksack::from_line(line);
+        let rucksack = Rucksack::from_line(line).unwrap();
 
         // WHEN
         let common_item = rucksack.common_item();
@@ -147,7 +201,7 @@ mod tests {
         let input = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg";
-        let inventory = Inventory::from_string(input);
+        let inventory = Inventory::from_string(input).unwrap();
         let group = &inventory.0[0];
 
         // WHEN
@@ -166,7 +220,7 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        let inventory = Inventory::from_string(input);
+        let inventory = Inventory::from_string(input).unwrap();
 
         // WHEN
         let priority = inventory.priority();
@@ -184,7 +238,7 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        let inventory = Inventory::from_string(input);
+        let inventory = Inventory::from_string(input).unwrap();
 
         // WHEN
         let badge_priority = inventory.badge_priority();
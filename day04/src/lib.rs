@@ -1,4 +1,4 @@
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+use common::{Error, Result, Solution};
 
 struct Assignment {
     low: usize,
@@ -7,10 +7,17 @@ struct Assignment {
 
 impl Assignment {
     fn from_string(input: &str) -> Result<Self> {
-        let (low, high) = input.split_once('-').ok_or("Cannot construct assignment")?;
+        let (low, high) = input
+            .split_once('-')
+            .ok_or_else(|| Error::parse(input, input, "a `low-high` section range"))?;
+        let section = |bound: &str| {
+            bound
+                .parse()
+                .map_err(|_| Error::parse(input, bound, "a section number"))
+        };
         Ok(Assignment {
-            low: low.parse()?,
-            high: high.parse()?,
+            low: section(low)?,
+            high: section(high)?,
         })
     }
 
@@ -26,10 +33,12 @@ struct Pair {
 
 impl Pair {
     fn from_string(input: &str) -> Result<Self> {
-        let (left, right) = input.split_once(',').ok_or("Cannot construct pair")?;
+        let (left, right) = input
+            .split_once(',')
+            .ok_or_else(|| Error::parse(input, input, "a comma-separated pair of ranges"))?;
         Ok(Pair {
-            left: Assignment::from_string(left)?,
-            right: Assignment::from_string(right)?,
+            left: Assignment::from_string(left).map_err(|e| e.within(input, left))?,
+            right: Assignment::from_string(right).map_err(|e| e.within(input, right))?,
         })
     }
 
@@ -49,7 +58,7 @@ impl WorkSheet {
         Ok(WorkSheet(
             input
                 .lines()
-                .map(Pair::from_string)
+                .map(|line| Pair::from_string(line).map_err(|e| e.within(input, line)))
                 .collect::<Result<Vec<_>>>()?,
         ))
     }
@@ -66,6 +75,23 @@ impl WorkSheet {
     }
 }
 
+impl Solution for WorkSheet {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        WorkSheet::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        Ok(self.count_fully_contains())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        Ok(self.count_overlaps())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,7 +106,7 @@ mod tests {
         let result = assignment1.fully_contains(&assignment2);
 
         // THEN
-        assert_eq!(true, result);
+        assert!(result);
     }
 
     #[test]
@@ -93,7 +119,7 @@ mod tests {
         let result = assignment1.fully_contains(&assignment2);
 
         // THEN
-        assert_eq!(false, result);
+        assert!(!result);
     }
 
     #[test]
@@ -108,7 +134,23 @@ mod tests {
         let result = pair.either_contains_the_other();
 
         // THEN
-        assert_eq!(true, result);
+        assert!(result);
+    }
+
+    #[test]
+    fn worksheet_from_string_invalid_section() {
+        // GIVEN
+        let input = "2-4,6-8
+2-3,4-five";
+
+        // WHEN
+        let result = WorkSheet::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 2, column 7: expected a section number, found `five`",
+            result.err().unwrap().to_string()
+        );
     }
 
     #[test]
@@ -1,7 +1,8 @@
+use common::{Error, Result, Solution};
 use lazy_static::lazy_static;
 use regex::Regex;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct Dock([Vec<u8>; 9]);
 
 impl Dock {
@@ -23,15 +24,15 @@ impl Dock {
     fn row_from_line(line: &str) -> Vec<Option<u8>> {
         line.as_bytes()
             .chunks(4)
-            .map(|chunk| match chunk[1] {
-                letter @ b'A'..=b'Z' => Some(letter),
+            .map(|chunk| match chunk.get(1) {
+                Some(letter @ b'A'..=b'Z') => Some(*letter),
                 _ => None,
             })
             .collect()
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 struct Command {
     amount: usize,
     src: usize,
@@ -39,51 +40,86 @@ struct Command {
 }
 
 impl Command {
-    fn from_string(input: &str) -> Self {
+    fn from_string(input: &str) -> Result<Self> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"move (\d+) from (\d) to (\d)").unwrap();
         }
 
-        let cap = RE.captures(input).unwrap();
-        Command {
-            amount: cap[1].parse().unwrap(),
-            src: cap[2].parse().unwrap(),
-            dst: cap[3].parse().unwrap(),
-        }
+        let cap = RE
+            .captures(input)
+            .ok_or_else(|| Error::parse(input, input, "a `move N from N to N` command"))?;
+        let number = |i: usize, expected: &str, valid: std::ops::RangeInclusive<usize>| {
+            let group = cap.get(i).map(|m| m.as_str()).unwrap_or_default();
+            group
+                .parse()
+                .ok()
+                .filter(|n| valid.contains(n))
+                .ok_or_else(|| Error::parse(input, group, expected))
+        };
+        Ok(Command {
+            amount: number(1, "a number of crates", 0..=usize::MAX)?,
+            src: number(2, "a stack number from 1 to 9", 1..=9)?,
+            dst: number(3, "a stack number from 1 to 9", 1..=9)?,
+        })
     }
 }
 
+#[derive(Clone)]
 pub struct Puzzle {
     dock: Dock,
     commands: Vec<Command>,
 }
 
 impl Puzzle {
-    pub fn from_string(input: &str) -> Self {
-        let (dock_input, command_input) = input.split_once("\n\n").unwrap();
-        Puzzle {
+    pub fn from_string(input: &str) -> Result<Self> {
+        let (dock_input, command_input) = input.split_once("\n\n").ok_or_else(|| {
+            Error::parse(
+                input,
+                &input[input.len()..],
+                "a blank line between the crates and the commands",
+            )
+        })?;
+        Ok(Puzzle {
             dock: Dock::from_string(dock_input),
-            commands: command_input.lines().map(Command::from_string).collect(),
-        }
+            commands: command_input
+                .lines()
+                .map(|line| Command::from_string(line).map_err(|e| e.within(input, line)))
+                .collect::<Result<_>>()?,
+        })
     }
 
-    pub fn apply_commands(&mut self) {
+    pub fn apply_commands(&mut self) -> Result<()> {
         for command in &self.commands {
             for _ in 0..command.amount {
-                let item = self.dock.0[command.src - 1].pop().unwrap();
+                let item = self.dock.0[command.src - 1]
+                    .pop()
+                    .ok_or_else(|| Self::not_enough_crates(command))?;
                 self.dock.0[command.dst - 1].push(item);
             }
         }
+        Ok(())
     }
 
-    pub fn apply_commands_stacked(&mut self) {
+    pub fn apply_commands_stacked(&mut self) -> Result<()> {
         for command in &self.commands {
             let src = &mut self.dock.0[command.src - 1];
-            let stack = src.split_off(src.len() - command.amount);
+            let remaining = src
+                .len()
+                .checked_sub(command.amount)
+                .ok_or_else(|| Self::not_enough_crates(command))?;
+            let stack = src.split_off(remaining);
 
             let dst = &mut self.dock.0[command.dst - 1];
             dst.extend(stack);
         }
+        Ok(())
+    }
+
+    fn not_enough_crates(command: &Command) -> Error {
+        Error::NoSolution(format!(
+            "cannot move {} crates from stack {}: not enough crates",
+            command.amount, command.src
+        ))
     }
 
     pub fn top_row(&self) -> String {
@@ -99,6 +135,27 @@ impl Puzzle {
     }
 }
 
+impl Solution for Puzzle {
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self> {
+        Puzzle::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        let mut puzzle = self.clone();
+        puzzle.apply_commands()?;
+        Ok(puzzle.top_row())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        let mut puzzle = self.clone();
+        puzzle.apply_commands_stacked()?;
+        Ok(puzzle.top_row())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,7 +218,7 @@ mod tests {
         let input = "move 3 from 5 to 2";
 
         // WHEN
-        let command = Command::from_string(input);
+        let command = Command::from_string(input).unwrap();
 
         // THEN
         assert_eq!(
@@ -174,6 +231,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn command_from_string_invalid_stack() {
+        // GIVEN
+        let input = "move 3 from 0 to 2";
+
+        // WHEN
+        let result = Command::from_string(input);
+
+        // THEN
+        assert_eq!(
+            Err(Error::Parse {
+                line: 1,
+                column: 13,
+                text: "0".to_string(),
+                expected: "a stack number from 1 to 9".to_string(),
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn puzzle_apply_commands_empty_stack() {
+        // GIVEN
+        let input = "
+[B]
+ 1   2   3   4   5   6   7   8   9 
+
+move 2 from 1 to 2";
+        let mut puzzle = Puzzle::from_string(input).unwrap();
+
+        // WHEN
+        let result = puzzle.apply_commands();
+
+        // THEN
+        assert_eq!(
+            Err(Error::NoSolution(
+                "cannot move 2 crates from stack 1: not enough crates".to_string()
+            )),
+            result
+        );
+    }
+
     #[test]
     fn puzzle_top_row_with_reverse() {
         // GIVEN
@@ -187,8 +286,8 @@ move 1 from 3 to 1
 move 2 from 7 to 2";
 
         // WHEN
-        let mut puzzle = Puzzle::from_string(input);
-        puzzle.apply_commands();
+        let mut puzzle = Puzzle::from_string(input).unwrap();
+        puzzle.apply_commands().unwrap();
         let top_row = puzzle.top_row();
 
         // THEN
@@ -208,8 +307,8 @@ move 1 from 3 to 1
 move 2 from 7 to 2";
 
         // WHEN
-        let mut puzzle = Puzzle::from_string(input);
-        puzzle.apply_commands_stacked();
+        let mut puzzle = Puzzle::from_string(input).unwrap();
+        puzzle.apply_commands_stacked().unwrap();
         let top_row = puzzle.top_row();
 
         // THEN
@@ -1,14 +1,20 @@
 use std::collections::HashSet;
 
-pub fn index_after_start_of_packet(input: &str) -> usize {
+use common::{Error, Result, Solution};
+
+pub struct Datastream(String);
+
+pub fn index_after_start_of_packet(input: &str) -> Result<usize> {
     index_after_unique_sequence(input, 4)
+        .ok_or_else(|| Error::NoSolution("no start-of-packet marker".to_string()))
 }
 
-pub fn index_after_start_of_message(input: &str) -> usize {
+pub fn index_after_start_of_message(input: &str) -> Result<usize> {
     index_after_unique_sequence(input, 14)
+        .ok_or_else(|| Error::NoSolution("no start-of-message marker".to_string()))
 }
 
-fn index_after_unique_sequence(input: &str, sequence_length: usize) -> usize {
+fn index_after_unique_sequence(input: &str, sequence_length: usize) -> Option<usize> {
     let start_of_packet = input
         .as_bytes()
         .windows(sequence_length)
@@ -16,9 +22,23 @@ fn index_after_unique_sequence(input: &str, sequence_length: usize) -> usize {
         .enumerate()
         .find(|(_idx, set)| set.len() == sequence_length);
 
-    match start_of_packet {
-        Some((idx, _)) => idx + sequence_length,
-        _ => 0,
+    start_of_packet.map(|(idx, _)| idx + sequence_length)
+}
+
+impl Solution for Datastream {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Datastream(input.trim().to_string()))
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        index_after_start_of_packet(&self.0)
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        index_after_start_of_message(&self.0)
     }
 }
 
@@ -35,7 +55,7 @@ mod tests {
         let idx = index_after_start_of_packet(input);
 
         // THEN
-        assert_eq!(7, idx);
+        assert_eq!(Ok(7), idx);
     }
 
     #[test]
@@ -47,6 +67,21 @@ mod tests {
         let idx = index_after_start_of_message(input);
 
         // THEN
-        assert_eq!(19, idx);
+        assert_eq!(Ok(19), idx);
+    }
+
+    #[test]
+    fn index_after_start_of_packet_missing() {
+        // GIVEN
+        let input = "aabbccdd";
+
+        // WHEN
+        let idx = index_after_start_of_packet(input);
+
+        // THEN
+        assert_eq!(
+            Err(Error::NoSolution("no start-of-packet marker".to_string())),
+            idx
+        );
     }
 }
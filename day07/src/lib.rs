@@ -2,6 +2,8 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::rc::{Rc, Weak};
 
+use common::{Error, Result, Solution};
+
 pub struct FileSystem {
     root: Rc<RefCell<DirEntry>>,
 }
@@ -13,7 +15,7 @@ struct DirEntry {
 }
 
 impl FileSystem {
-    pub fn from_string(input: &str) -> Self {
+    pub fn from_string(input: &str) -> Result<Self> {
         let root = Rc::new(RefCell::new(DirEntry {
             parent: Weak::new(),
             subdirs: BTreeMap::new(),
@@ -21,17 +23,27 @@ impl FileSystem {
         }));
         {
             let mut current = Rc::clone(&root);
+            let mut tokens = input.split("$ ");
 
             // First token is always empty, second is always "cd /"
-            for token in input.split("$ ").skip(2) {
-                match &token[..2] {
-                    "ls" => Self::handle_ls(Rc::clone(&current), token),
-                    "cd" => current = Self::get_new_dir(current, token),
-                    _ => unreachable!("unknown command"),
+            if tokens.next() != Some("") || tokens.next().map(str::trim_end) != Some("cd /") {
+                return Err(Error::parse(
+                    input,
+                    input,
+                    "a session starting with `$ cd /`",
+                ));
+            }
+            for token in tokens {
+                let result = match token.get(..2) {
+                    Some("ls") => Self::handle_ls(Rc::clone(&current), token),
+                    Some("cd") => Self::get_new_dir(Rc::clone(&current), token)
+                        .map(|new_dir| current = new_dir),
+                    _ => Err(Error::parse(token, token, "an `ls` or `cd` command")),
                 };
+                result.map_err(|e| e.within(input, token))?;
             }
         }
-        FileSystem { root }
+        Ok(FileSystem { root })
     }
 
     pub fn total_size_under_100k(&self) -> usize {
@@ -42,26 +54,26 @@ impl FileSystem {
         dirs.iter().sum()
     }
 
-    pub fn smallest_dir_big_enough(&self) -> usize {
+    pub fn smallest_dir_big_enough(&self) -> Result<usize> {
         let mut dirs = Vec::new();
-        self.root
+        let current_occupation = self
+            .root
             .borrow()
-            .all_dirs_sizes_under_threshold(&mut dirs, 70000000);
-
-        // By recursion, the outer dir is the last appended
-        let current_occupation = dirs.last().unwrap();
+            .all_dirs_sizes_under_threshold(&mut dirs, usize::MAX);
 
         dirs.iter()
             .copied()
             .filter(|size| current_occupation - size < 40000000)
             .min()
-            .unwrap()
+            .ok_or_else(|| Error::NoSolution("no directory is big enough".to_string()))
     }
 
-    fn handle_ls(current_dir: Rc<RefCell<DirEntry>>, token: &str) {
+    fn handle_ls(current_dir: Rc<RefCell<DirEntry>>, token: &str) -> Result<()> {
         // First token is the "ls" command, we skip to the output
         for line in token.lines().skip(1) {
-            let (left, right) = line.split_once(' ').unwrap();
+            let (left, right) = line
+                .split_once(' ')
+                .ok_or_else(|| Error::parse(token, line, "`dir NAME` or `SIZE NAME`"))?;
             if left == "dir" {
                 current_dir.borrow_mut().subdirs.insert(
                     right.to_string(),
@@ -72,21 +84,32 @@ impl FileSystem {
                     })),
                 );
             } else {
+                let size = left
+                    .parse()
+                    .map_err(|_| Error::parse(token, left, "a file size"))?;
                 current_dir
                     .borrow_mut()
                     .files
-                    .insert(right.to_string(), left.parse().unwrap());
+                    .insert(right.to_string(), size);
             }
         }
+        Ok(())
     }
 
-    fn get_new_dir(current_dir: Rc<RefCell<DirEntry>>, token: &str) -> Rc<RefCell<DirEntry>> {
-        let (_, arg) = token.trim().split_once(' ').unwrap();
-        if arg == ".." {
-            return current_dir.borrow().parent.upgrade().unwrap();
+    fn get_new_dir(
+        current_dir: Rc<RefCell<DirEntry>>,
+        token: &str,
+    ) -> Result<Rc<RefCell<DirEntry>>> {
+        let (_, arg) = token
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| Error::parse(token, token, "a `cd DIR` command"))?;
+        let new_dir = if arg == ".." {
+            current_dir.borrow().parent.upgrade()
         } else {
-            return Rc::clone(&current_dir.borrow().subdirs[arg]);
-        }
+            current_dir.borrow().subdirs.get(arg).cloned()
+        };
+        new_dir.ok_or_else(|| Error::parse(token, arg, "a known directory"))
     }
 }
 
@@ -115,6 +138,23 @@ impl DirEntry {
     }
 }
 
+impl Solution for FileSystem {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        FileSystem::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        Ok(self.total_size_under_100k())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        self.smallest_dir_big_enough()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,7 +176,7 @@ mod tests {
             for (child_name, child_entry) in &self.subdirs {
                 child_entry
                     .borrow()
-                    .dump(writer, &child_name, indent_size + 2);
+                    .dump(writer, child_name, indent_size + 2);
             }
         }
     }
@@ -150,7 +190,7 @@ mod tests {
             "};
 
         // WHEN
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // THEN
         let mut output = String::new();
@@ -173,7 +213,7 @@ mod tests {
             "};
 
         // WHEN
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // THEN
         let mut output = String::new();
@@ -197,7 +237,7 @@ mod tests {
             "};
 
         // WHEN
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // THEN
         let mut output = String::new();
@@ -224,7 +264,7 @@ mod tests {
             "};
 
         // WHEN
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // THEN
         let mut output = String::new();
@@ -257,7 +297,7 @@ mod tests {
             "};
 
         // WHEN
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // THEN
         let mut output = String::new();
@@ -304,7 +344,7 @@ mod tests {
             "};
 
         // WHEN
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // THEN
         let mut output = String::new();
@@ -330,6 +370,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filesystem_from_string_unknown_dir() {
+        // GIVEN
+        let input = indoc! {"
+            $ cd /
+            $ ls
+            dir a
+            $ cd b
+            $ ls
+            "};
+
+        // WHEN
+        let result = FileSystem::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 4, column 6: expected a known directory, found `b`",
+            result.err().unwrap().to_string()
+        );
+    }
+
     #[test]
     fn filesystem_total_size_under_100k() {
         // GIVEN
@@ -358,7 +419,7 @@ mod tests {
             5626152 d.ext
             7214296 k
             "};
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // WHEN
         let total_size = fs.total_size_under_100k();
@@ -395,12 +456,12 @@ mod tests {
             5626152 d.ext
             7214296 k
             "};
-        let fs = FileSystem::from_string(input);
+        let fs = FileSystem::from_string(input).unwrap();
 
         // WHEN
         let total_size = fs.smallest_dir_big_enough();
 
         // THEN
-        assert_eq!(24933642, total_size);
+        assert_eq!(Ok(24933642), total_size);
     }
 }
@@ -1,3 +1,5 @@
+use common::{Error, Result, Solution};
+
 pub struct Forest {
     width: usize,
     height: usize,
@@ -5,15 +7,34 @@ pub struct Forest {
 }
 
 impl Forest {
-    pub fn from_string(input: &str) -> Self {
-        let width = input.chars().position(|x| x == '\n').unwrap();
-        let grid : Vec<u32> = input
-            .chars()
-            .filter(|x| *x != '\n')
-            .flat_map(|tree| tree.to_digit(10))
-            .collect();
+    pub fn from_string(input: &str) -> Result<Self> {
+        let width = input.lines().next().map(str::len).unwrap_or_default();
+        if width == 0 {
+            return Err(Error::parse(input, input, "a grid of tree heights"));
+        }
+
+        let mut grid = Vec::new();
+        for line in input.lines() {
+            if line.len() != width {
+                return Err(Error::parse(input, line, format!("a row of {width} trees")));
+            }
+            for (idx, tree) in line.char_indices() {
+                let height = tree.to_digit(10).ok_or_else(|| {
+                    Error::parse(
+                        input,
+                        &line[idx..idx + tree.len_utf8()],
+                        "a tree height digit",
+                    )
+                })?;
+                grid.push(height);
+            }
+        }
         let height = grid.len() / width;
-        Forest { width, grid, height}
+        Ok(Forest {
+            width,
+            grid,
+            height,
+        })
     }
 
     pub fn number_of_visibles(&self) -> usize {
@@ -26,7 +47,7 @@ impl Forest {
         (0..self.grid.len())
             .map(|idx| self.scenic_score(idx))
             .max()
-            .unwrap()
+            .unwrap_or_default()
     }
 
     fn idx_to_coords(&self, idx: usize) -> (usize, usize) {
@@ -57,7 +78,6 @@ impl Forest {
             * self.viewing_distance_bottom(i, j)
     }
 
-
     fn is_visible_from_left(&self, mut i: usize, j: usize) -> bool {
         let tree = self.get(i, j);
 
@@ -86,7 +106,7 @@ impl Forest {
         let tree = self.get(i, j);
 
         while j > 0 {
-            if self.get(i, j - 1)>= tree {
+            if self.get(i, j - 1) >= tree {
                 return false;
             }
             j -= 1;
@@ -163,6 +183,23 @@ impl Forest {
     }
 }
 
+impl Solution for Forest {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Forest::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        Ok(self.number_of_visibles())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        Ok(self.highest_scenic_score())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,7 +216,7 @@ mod tests {
             35390
         "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.number_of_visibles();
@@ -188,6 +225,25 @@ mod tests {
         assert_eq!(21, num);
     }
 
+    #[test]
+    fn forest_from_string_ragged_row() {
+        // GIVEN
+        let input = indoc! {"
+            30373
+            2551
+            65332
+        "};
+
+        // WHEN
+        let result = Forest::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 2, column 1: expected a row of 5 trees, found `2551`",
+            result.err().unwrap().to_string()
+        );
+    }
+
     #[test]
     fn forest_viewing_distance_left() {
         // GIVEN
@@ -199,7 +255,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_left(2, 1);
@@ -219,7 +275,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_left(2, 3);
@@ -239,7 +295,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_right(2, 1);
@@ -259,7 +315,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_right(2, 3);
@@ -279,7 +335,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_top(2, 1);
@@ -299,7 +355,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_top(2, 3);
@@ -319,7 +375,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_bottom(2, 1);
@@ -339,7 +395,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.viewing_distance_bottom(2, 3);
@@ -359,7 +415,7 @@ mod tests {
         35390
     "};
 
-        let forest = Forest::from_string(input);
+        let forest = Forest::from_string(input).unwrap();
 
         // WHEN
         let num = forest.highest_scenic_score();
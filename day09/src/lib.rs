@@ -1,7 +1,7 @@
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
-
 use std::collections::HashSet;
 
+use common::{Error, Result, Solution};
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct Point {
     x: isize,
@@ -35,21 +35,38 @@ struct Command {
 
 impl Command {
     fn new(line: &str) -> Result<Self> {
-        let (dir, amount) = line.split_once(' ').ok_or("Cannot split")?;
-        let dir = match dir {
+        let (dir, amount) = line
+            .split_once(' ')
+            .ok_or_else(|| Error::parse(line, line, "a direction and a number of steps"))?;
+        let direction = match dir {
             "L" => Point { x: -1, y: 0 },
             "U" => Point { x: 0, y: 1 },
             "R" => Point { x: 1, y: 0 },
             "D" => Point { x: 0, y: -1 },
-            _ => return Err(Box::from("unknown command")),
+            _ => return Err(Error::parse(line, dir, "a direction (L, U, R or D)")),
         };
         Ok(Command {
-            direction: dir,
-            amount: amount.parse()?,
+            direction,
+            amount: amount
+                .parse()
+                .map_err(|_| Error::parse(line, amount, "a number of steps"))?,
         })
     }
 }
 
+pub struct Motions(Vec<Command>);
+
+impl Motions {
+    pub fn from_string(input: &str) -> Result<Self> {
+        Ok(Motions(
+            input
+                .lines()
+                .map(|line| Command::new(line).map_err(|e| e.within(input, line)))
+                .collect::<Result<_>>()?,
+        ))
+    }
+}
+
 pub struct Rope {
     knots: Vec<Point>,
     visited_by_tail: HashSet<Point>,
@@ -64,15 +81,17 @@ impl Rope {
     }
 
     pub fn apply_from_string(&mut self, input: &str) -> Result<()> {
-        let commands: Vec<Command> = input.lines().map(Command::new).collect::<Result<_>>()?;
+        self.apply(&Motions::from_string(input)?);
+        Ok(())
+    }
 
-        for command in commands {
+    pub fn apply(&mut self, motions: &Motions) {
+        for command in &motions.0 {
             for _ in 0..command.amount {
                 self.knots[0] += command.direction;
                 self.update_knots();
             }
         }
-        Ok(())
     }
 
     pub fn unique_tail_positions(&self) -> usize {
@@ -101,6 +120,27 @@ impl Rope {
     }
 }
 
+impl Solution for Motions {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Motions::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        let mut rope = Rope::new(2);
+        rope.apply(self);
+        Ok(rope.unique_tail_positions())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        let mut rope = Rope::new(10);
+        rope.apply(self);
+        Ok(rope.unique_tail_positions())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,6 +168,25 @@ mod tests {
         assert_eq!(13, rope.unique_tail_positions());
     }
 
+    #[test]
+    fn rope_apply_from_string_unknown_direction() {
+        // GIVEN
+        let input = indoc! {"
+            R 4
+            X 4
+        "};
+        let mut rope = Rope::new(2);
+
+        // WHEN
+        let result = rope.apply_from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 2, column 1: expected a direction (L, U, R or D), found `X`",
+            result.unwrap_err().to_string()
+        );
+    }
+
     #[test]
     fn rope10_unique_tail_positions() {
         // GIVEN
@@ -0,0 +1,326 @@
+//! The screen of the handheld device, and the ways to show it.
+
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use common::{Error, Result};
+
+use crate::vm::{Observer, Tick};
+
+/// Size of the screen and of the sprite drawn on it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Geometry {
+    pub width: usize,
+    pub height: usize,
+    pub sprite_width: usize,
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Geometry {
+            width: 40,
+            height: 6,
+            sprite_width: 3,
+        }
+    }
+}
+
+impl Geometry {
+    /// Columns covered by the sprite when the X register holds `x`.
+    pub fn sprite(&self, x: i32) -> RangeInclusive<i32> {
+        let left = (self.sprite_width as i32 - 1) / 2;
+        let right = self.sprite_width as i32 / 2;
+        x.saturating_sub(left)..=x.saturating_add(right)
+    }
+
+    /// Column the beam draws during a cycle.
+    pub fn column(&self, cycle: usize) -> usize {
+        (cycle - 1) % self.width
+    }
+}
+
+/// Draws a pixel whenever the sprite, centered on X, is under the beam.
+#[derive(Debug, Clone)]
+pub struct Crt {
+    geometry: Geometry,
+    pixels: Vec<bool>,
+}
+
+impl Crt {
+    pub fn new() -> Self {
+        Crt::with_geometry(Geometry::default()).expect("the default geometry is valid")
+    }
+
+    pub fn with_geometry(geometry: Geometry) -> Result<Self> {
+        if geometry.width == 0 || geometry.sprite_width == 0 {
+            return Err(Error::InvalidArgument(
+                "the screen and the sprite should be at least 1 pixel wide".to_string(),
+            ));
+        }
+        Ok(Crt {
+            geometry,
+            pixels: vec![false; geometry.width * geometry.height],
+        })
+    }
+
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.pixels.chunks(self.geometry.width)
+    }
+
+    pub fn render(&self) -> String {
+        self.render_with(Renderer::Text)
+    }
+
+    pub fn render_with(&self, renderer: Renderer) -> String {
+        match renderer {
+            Renderer::Text => self.render_text(),
+            Renderer::Blocks => self.render_blocks(),
+            Renderer::Ansi => self.render_ansi(),
+            Renderer::Pbm => self.render_netpbm("P1", "", '1', '0'),
+            Renderer::Pgm => self.render_netpbm("P2", "1\n", '0', '1'),
+        }
+    }
+
+    fn render_text(&self) -> String {
+        self.rows()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Packs two rows of pixels into each line, using half block characters.
+    fn render_blocks(&self) -> String {
+        let rows: Vec<_> = self.rows().collect();
+        rows.chunks(2)
+            .map(|pair| {
+                (0..self.geometry.width)
+                    .map(|column| {
+                        let top = pair[0][column];
+                        let bottom = pair.get(1).map(|row| row[column]).unwrap_or(false);
+                        match (top, bottom) {
+                            (true, true) => '█',
+                            (true, false) => '▀',
+                            (false, true) => '▄',
+                            (false, false) => ' ',
+                        }
+                    })
+                    .collect()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn render_ansi(&self) -> String {
+        self.rows()
+            .map(|row| {
+                let mut line = String::new();
+                for &lit in row {
+                    let colour = if lit { 42 } else { 40 };
+                    write!(line, "\x1b[{colour}m  ").unwrap();
+                }
+                line + "\x1b[0m"
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Plain (ASCII) netpbm image, with dark letters on a light background.
+    fn render_netpbm(&self, magic: &str, max_value: &str, lit: char, unlit: char) -> String {
+        let mut image = format!(
+            "{magic}\n{} {}\n{max_value}",
+            self.geometry.width, self.geometry.height
+        );
+        for row in self.rows() {
+            let values: Vec<_> = row
+                .iter()
+                .map(|&pixel| if pixel { lit } else { unlit }.to_string())
+                .collect();
+            image += &values.join(" ");
+            image.push('\n');
+        }
+        image
+    }
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Observer for Crt {
+    fn on_cycle(&mut self, tick: &Tick) {
+        let column = self.geometry.column(tick.cycle);
+        if self
+            .geometry
+            .sprite(tick.registers.x)
+            .contains(&(column as i32))
+        {
+            // Programs running longer than the screen simply stop drawing
+            if let Some(pixel) = self.pixels.get_mut(tick.cycle - 1) {
+                *pixel = true;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Renderer {
+    /// `#` and `.` characters
+    Text,
+    /// Unicode half blocks, two rows of pixels per line
+    Blocks,
+    /// Coloured cells for ANSI terminals
+    Ansi,
+    /// Plain PBM image
+    Pbm,
+    /// Plain PGM image
+    Pgm,
+}
+
+impl FromStr for Renderer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Renderer::Text),
+            "blocks" => Ok(Renderer::Blocks),
+            "ansi" => Ok(Renderer::Ansi),
+            "pbm" => Ok(Renderer::Pbm),
+            "pgm" => Ok(Renderer::Pgm),
+            _ => Err(Error::InvalidArgument(format!(
+                "expected one of `text`, `blocks`, `ansi`, `pbm` or `pgm`, found `{s}`"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{Machine, Program};
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn draw(geometry: Geometry, input: &str) -> Crt {
+        let mut crt = Crt::with_geometry(geometry).unwrap();
+        Machine::new(Program::parse(input).unwrap())
+            .run(&mut [&mut crt])
+            .unwrap();
+        crt
+    }
+
+    #[test]
+    fn geometry_sprite() {
+        // GIVEN
+        let narrow = Geometry {
+            sprite_width: 1,
+            ..Geometry::default()
+        };
+        let wide = Geometry {
+            sprite_width: 4,
+            ..Geometry::default()
+        };
+
+        // WHEN
+        let sprites = (
+            narrow.sprite(5),
+            Geometry::default().sprite(5),
+            wide.sprite(5),
+        );
+
+        // THEN
+        assert_eq!((5..=5, 4..=6, 4..=7), sprites);
+    }
+
+    #[test]
+    fn crt_custom_geometry() {
+        // GIVEN
+        let geometry = Geometry {
+            width: 4,
+            height: 3,
+            sprite_width: 3,
+        };
+        let input = indoc! {"
+            noop
+            addx 1
+            noop
+            addx 1
+            noop
+            addx -3
+            noop
+            addx 1
+            noop
+        "};
+
+        // WHEN
+        let crt = draw(geometry, input);
+
+        // THEN
+        let expected = indoc! {"
+            ####
+            .###
+            .#.."};
+        assert_eq!(expected, crt.render());
+    }
+
+    #[test]
+    fn crt_renderers() {
+        // GIVEN
+        let geometry = Geometry {
+            width: 3,
+            height: 3,
+            sprite_width: 1,
+        };
+        let crt = draw(geometry, "noop\nnoop\naddx 1\naddx -2\nnoop\nnoop\nnoop\n");
+
+        // WHEN
+        let blocks = crt.render_with(Renderer::Blocks);
+        let pbm = crt.render_with(Renderer::Pbm);
+        let pgm = crt.render_with(Renderer::Pgm);
+
+        // THEN
+        assert_eq!(".#.\n..#\n#..", crt.render());
+        assert_eq!(" ▀▄\n▀  ", blocks);
+        assert_eq!("P1\n3 3\n0 1 0\n0 0 1\n1 0 0\n", pbm);
+        assert_eq!("P2\n3 3\n1\n1 0 1\n1 1 0\n0 1 1\n", pgm);
+    }
+
+    #[test]
+    fn crt_zero_width() {
+        // GIVEN
+        let geometry = Geometry {
+            width: 0,
+            ..Geometry::default()
+        };
+
+        // WHEN
+        let crt = Crt::with_geometry(geometry);
+
+        // THEN
+        assert_eq!(
+            "invalid argument: the screen and the sprite should be at least 1 pixel wide",
+            crt.unwrap_err().to_string()
+        );
+    }
+    #[test]
+    fn renderer_unknown_name() {
+        // GIVEN
+        let name = "svg";
+
+        // WHEN
+        let renderer = name.parse::<Renderer>();
+
+        // THEN
+        assert_eq!(
+            "invalid argument: expected one of `text`, `blocks`, `ansi`, `pbm` or `pgm`, \
+             found `svg`",
+            renderer.unwrap_err().to_string()
+        );
+    }
+}
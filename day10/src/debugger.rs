@@ -0,0 +1,271 @@
+//! Tracing and stepping through a program, to understand a garbled picture.
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use common::Result;
+
+use crate::vm::{Instruction, Machine, Observer, Program, Registers, Tick};
+use crate::{Crt, Geometry};
+
+/// What happened on the screen during a single cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRow {
+    pub cycle: usize,
+    pub instruction: Instruction,
+    pub x: i32,
+    pub sprite: RangeInclusive<i32>,
+    pub column: usize,
+    pub lit: bool,
+}
+
+impl TraceRow {
+    fn new(geometry: &Geometry, tick: &Tick) -> Self {
+        let sprite = geometry.sprite(tick.registers.x);
+        let column = geometry.column(tick.cycle);
+        TraceRow {
+            cycle: tick.cycle,
+            instruction: tick.instruction,
+            x: tick.registers.x,
+            lit: sprite.contains(&(column as i32)),
+            sprite,
+            column,
+        }
+    }
+}
+
+impl Display for TraceRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>5}  {:<9}  {:>4}  {:>3}..{:<3}  {:>6}  {}",
+            self.cycle,
+            self.instruction.opcode.to_string(),
+            self.x,
+            self.sprite.start(),
+            self.sprite.end(),
+            self.column,
+            if self.lit { '#' } else { '.' }
+        )
+    }
+}
+
+/// Observer keeping a row per cycle.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    geometry: Geometry,
+    rows: Vec<TraceRow>,
+}
+
+impl Trace {
+    pub fn new(geometry: Geometry) -> Self {
+        Trace {
+            geometry,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn rows(&self) -> &[TraceRow] {
+        &self.rows
+    }
+}
+
+impl Observer for Trace {
+    fn on_cycle(&mut self, tick: &Tick) {
+        self.rows.push(TraceRow::new(&self.geometry, tick));
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "cycle  instr         X   sprite    column  pixel")?;
+        for row in &self.rows {
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs a program one cycle at a time, drawing and tracing as it goes.
+pub struct Debugger {
+    machine: Machine,
+    crt: Crt,
+    trace: Trace,
+    breakpoints: Vec<i32>,
+}
+
+impl Debugger {
+    pub fn new(program: Program) -> Self {
+        Debugger::with_geometry(program, Geometry::default())
+            .expect("the default geometry is valid")
+    }
+
+    pub fn with_geometry(program: Program, geometry: Geometry) -> Result<Self> {
+        Ok(Debugger {
+            machine: Machine::new(program),
+            crt: Crt::with_geometry(geometry)?,
+            trace: Trace::new(geometry),
+            breakpoints: Vec::new(),
+        })
+    }
+
+    /// Stops `resume` after any cycle during which X holds this value.
+    pub fn break_on_x(&mut self, x: i32) {
+        self.breakpoints.push(x);
+    }
+
+    /// Runs a single cycle, or returns `None` if the program is over.
+    pub fn step(&mut self) -> Result<Option<&TraceRow>> {
+        let tick = self.machine.step(&mut [&mut self.crt, &mut self.trace])?;
+        Ok(tick.and(self.trace.rows.last()))
+    }
+
+    /// Runs until `cycle` has been completed, or returns `None` if the program is
+    /// over before.
+    pub fn run_to_cycle(&mut self, cycle: usize) -> Result<Option<&TraceRow>> {
+        while self.machine.cycle() < cycle {
+            if self.step()?.is_none() {
+                return Ok(None);
+            }
+        }
+        Ok(self.trace.rows.last())
+    }
+
+    /// Runs until a breakpoint is hit, returning the cycle that hit it.
+    pub fn resume(&mut self) -> Result<Option<&TraceRow>> {
+        loop {
+            let Some(x) = self.step()?.map(|row| row.x) else {
+                return Ok(None);
+            };
+            if self.breakpoints.contains(&x) {
+                return Ok(self.trace.rows.last());
+            }
+        }
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.machine.registers()
+    }
+
+    pub fn cycle(&self) -> usize {
+        self.machine.cycle()
+    }
+
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    pub fn crt(&self) -> &Crt {
+        &self.crt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::Opcode;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn debugger() -> Debugger {
+        let input = indoc! {"
+            addx 15
+            addx -11
+            addx 6
+            addx -3
+            noop
+        "};
+        Debugger::new(Program::parse(input).unwrap())
+    }
+
+    #[test]
+    fn debugger_step() {
+        // GIVEN
+        let mut debugger = debugger();
+
+        // WHEN
+        let rows: Vec<_> = std::iter::from_fn(|| debugger.step().unwrap().cloned())
+            .map(|row| (row.cycle, row.x, row.lit))
+            .collect();
+
+        // THEN
+        assert_eq!(
+            vec![
+                (1, 1, true),
+                (2, 1, true),
+                (3, 16, false),
+                (4, 16, false),
+                (5, 5, true),
+                (6, 5, true),
+                (7, 11, false),
+                (8, 11, false),
+                (9, 8, true),
+            ],
+            rows
+        );
+        assert_eq!(8, debugger.registers().x);
+    }
+
+    #[test]
+    fn debugger_run_to_cycle() {
+        // GIVEN
+        let mut debugger = debugger();
+
+        // WHEN
+        let row = debugger.run_to_cycle(4).unwrap().cloned();
+
+        // THEN
+        assert_eq!(
+            Some(TraceRow {
+                cycle: 4,
+                instruction: Instruction {
+                    line: 2,
+                    opcode: Opcode::Addx(-11)
+                },
+                x: 16,
+                sprite: 15..=17,
+                column: 3,
+                lit: false,
+            }),
+            row
+        );
+        assert_eq!(4, debugger.trace().rows().len());
+        assert_eq!(Ok(None), debugger.run_to_cycle(100));
+    }
+
+    #[test]
+    fn debugger_break_on_x() {
+        // GIVEN
+        let mut debugger = debugger();
+        debugger.break_on_x(5);
+        debugger.break_on_x(8);
+
+        // WHEN
+        let mut resume = || debugger.resume().unwrap().map(|row| row.cycle);
+        let (first, second, third, end) = (resume(), resume(), resume(), resume());
+
+        // THEN
+        assert_eq!(
+            (Some(5), Some(6), Some(9), None),
+            (first, second, third, end)
+        );
+    }
+
+    #[test]
+    fn trace_display() {
+        // GIVEN
+        let mut debugger = debugger();
+
+        // WHEN
+        debugger.run_to_cycle(3).unwrap();
+
+        // THEN
+        let expected = indoc! {"
+            cycle  instr         X   sprite    column  pixel
+                1  addx 15       1    0..2         0  #
+                2  addx 15       1    0..2         1  #
+                3  addx -11     16   15..17        2  .
+        "};
+        assert_eq!(expected, debugger.trace().to_string());
+    }
+}
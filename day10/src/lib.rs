@@ -1,62 +1,107 @@
+use std::collections::BTreeMap;
+
+use common::{Error, Result, Solution};
+use vm::{Machine, Observer, Program, Tick};
+
+pub use crt::{Crt, Geometry, Renderer};
+
+pub mod crt;
+pub mod debugger;
+pub mod ocr;
+pub mod vm;
+
+/// Records the signal strength during a few interesting cycles.
+pub struct SignalSampler {
+    cycles: Vec<usize>,
+    strengths: BTreeMap<usize, i64>,
+}
+
+impl SignalSampler {
+    pub fn new(cycles: &[usize]) -> Self {
+        SignalSampler {
+            cycles: cycles.to_vec(),
+            strengths: BTreeMap::new(),
+        }
+    }
+
+    pub fn sum(&self) -> Result<i32> {
+        let sum = self
+            .cycles
+            .iter()
+            .map(|cycle| {
+                self.strengths.get(cycle).copied().ok_or_else(|| {
+                    Error::NoSolution(format!("the program stops before cycle {cycle}"))
+                })
+            })
+            .sum::<Result<i64>>()?;
+        i32::try_from(sum).map_err(|_| {
+            Error::NoSolution(format!("the sum of the signal strengths, {sum}, overflows"))
+        })
+    }
+}
+
+impl Observer for SignalSampler {
+    fn on_cycle(&mut self, tick: &Tick) {
+        if self.cycles.contains(&tick.cycle) {
+            self.strengths
+                .insert(tick.cycle, tick.cycle as i64 * i64::from(tick.registers.x));
+        }
+    }
+}
+
 pub struct Cpu {
-    x: i32,
-    history: Vec<i32>,
-    crt: [u8; 240],
+    sampler: SignalSampler,
+    crt: Crt,
 }
 
 impl Cpu {
     pub fn new() -> Self {
-        Cpu {
-            x: 1,
-            history: vec![0], // Dummy value to start at 1
-            crt: [b'.'; 240],
-        }
+        Cpu::with_geometry(Geometry::default()).expect("the default geometry is valid")
     }
 
-    pub fn run(&mut self, input: &str) {
-        input.lines().for_each(|line| self.run_command(line));
+    pub fn with_geometry(geometry: Geometry) -> Result<Self> {
+        Ok(Cpu {
+            sampler: SignalSampler::new(&[20, 60, 100, 140, 180, 220]),
+            crt: Crt::with_geometry(geometry)?,
+        })
     }
 
-    pub fn sum_of_interesting_signal_strengths(&self) -> i32 {
-        [20, 60, 100, 140, 180, 220]
-            .iter()
-            .map(|cycle| self.signal_strength(*cycle))
-            .sum()
+    pub fn run(&mut self, input: &str) -> Result<()> {
+        let program = Program::parse(input)?;
+        Machine::new(program).run(&mut [&mut self.sampler, &mut self.crt])
     }
 
-    pub fn crt(&self) -> String {
-        self.crt
-            .chunks(40)
-            .flat_map(std::str::from_utf8)
-            .collect::<Vec<_>>()
-            .join("\n")
+    pub fn sum_of_interesting_signal_strengths(&self) -> Result<i32> {
+        self.sampler.sum()
     }
 
-    fn run_command(&mut self, command: &str) {
-        self.update_crt();
-        self.history.push(self.x);
-        if let Some(("addx", n)) = command.split_once(' ') {
-            self.update_crt();
-            self.history.push(self.x);
+    pub fn crt(&self) -> String {
+        self.crt.render()
+    }
+}
 
-            let n: i32 = n.parse().unwrap();
-            self.x += n;
-        }
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
     }
+}
+
+impl Solution for Cpu {
+    type Part1 = i32;
+    type Part2 = String;
 
-    fn signal_strength(&self, cycle: usize) -> i32 {
-        cycle as i32 * self.history[cycle]
+    fn parse(input: &str) -> Result<Self> {
+        let mut cpu = Cpu::new();
+        cpu.run(input)?;
+        Ok(cpu)
     }
 
-    fn update_crt(&mut self) {
-        let pos = (self.current_cycle()) % 40;
-        if [self.x - 1, self.x, self.x + 1].contains(&(pos as i32)) {
-            self.crt[self.current_cycle()] = b'#';
-        }
+    fn part1(&self) -> Result<Self::Part1> {
+        self.sum_of_interesting_signal_strengths()
     }
 
-    fn current_cycle(&self) -> usize {
-        self.history.len() - 1
+    fn part2(&self) -> Result<Self::Part2> {
+        ocr::decode(&self.crt())
     }
 }
 
@@ -66,12 +111,22 @@ mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
 
-    impl Cpu {
-        fn x_history(&self) -> &[i32] {
-            &self.history
+    struct History(Vec<i32>);
+
+    impl Observer for History {
+        fn on_cycle(&mut self, tick: &Tick) {
+            self.0.push(tick.registers.x);
         }
     }
 
+    fn x_history(input: &str) -> Vec<i32> {
+        let mut history = History(vec![0]); // Dummy value to start at 1
+        Machine::new(Program::parse(input).unwrap())
+            .run(&mut [&mut history])
+            .unwrap();
+        history.0
+    }
+
     #[test]
     fn cpu_x_history() {
         // GIVEN
@@ -81,13 +136,66 @@ mod tests {
             addx -5
             noop
         "};
+
+        // WHEN
+        let history = x_history(input);
+
+        // THEN
+        assert_eq!(vec![0, 1, 1, 1, 4, 4, -1], history);
+    }
+
+    #[test]
+    fn cpu_run_unknown_instruction() {
+        // GIVEN
+        let input = indoc! {"
+            noop
+            addx 3
+            subx 5
+        "};
         let mut cpu = Cpu::new();
 
         // WHEN
-        cpu.run(input);
+        let result = cpu.run(input);
+
+        // THEN
+        assert_eq!(
+            "line 3, column 1: expected `noop` or `addx N`, found `subx 5`",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn cpu_run_x_overflow() {
+        // GIVEN
+        let input = indoc! {"
+            addx 2147483647
+            addx 1
+        "};
+        let mut cpu = Cpu::new();
+
+        // WHEN
+        let result = cpu.run(input);
+
+        // THEN
+        assert_eq!(
+            "no solution: line 1: `addx 2147483647` overflows the X register",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn cpu_huge_signal_strengths() {
+        // GIVEN X reaches its largest value before every interesting cycle
+        let input = "addx 2147483646\n".to_string() + &"noop\n".repeat(218);
+
+        // WHEN
+        let cpu = Cpu::parse(&input).unwrap();
 
         // THEN
-        assert_eq!(vec![0, 1, 1, 1, 4, 4, -1], cpu.x_history());
+        assert_eq!(
+            "no solution: the sum of the signal strengths, 1546188225840, overflows",
+            cpu.part1().unwrap_err().to_string()
+        );
     }
 
     #[test]
@@ -244,10 +352,10 @@ mod tests {
         let mut cpu = Cpu::new();
 
         // WHEN
-        cpu.run(input);
+        cpu.run(input).unwrap();
 
         // THEN
-        assert_eq!(13140, cpu.sum_of_interesting_signal_strengths());
+        assert_eq!(Ok(13140), cpu.sum_of_interesting_signal_strengths());
     }
 
     #[test]
@@ -404,7 +512,7 @@ mod tests {
         let mut cpu = Cpu::new();
 
         // WHEN
-        cpu.run(input);
+        cpu.run(input).unwrap();
 
         // THEN
         let expected_crt = indoc! {"
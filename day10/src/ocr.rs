@@ -0,0 +1,116 @@
+//! Reading the letters drawn on the CRT.
+//!
+//! Every Advent of Code puzzle draws its letters with the same font: glyphs
+//! are 4 pixels wide and 6 pixels high, separated by a blank column.
+
+use common::{Error, Result};
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_STRIDE: usize = GLYPH_WIDTH + 1;
+
+#[rustfmt::skip]
+const FONT: [(char, [&str; 6]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###.", ".#..", ".#..", ".#..", ".#..", "###."]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Decodes a picture of `#` and `.` pixels, one line per row, into its letters.
+pub fn decode(picture: &str) -> Result<String> {
+    let rows: Vec<&[u8]> = picture.lines().map(str::as_bytes).collect();
+    if rows.len() != 6 {
+        return Err(Error::NoSolution(format!(
+            "the picture should be 6 pixels high, not {}",
+            rows.len()
+        )));
+    }
+    let width = rows[0].len();
+    if rows.iter().any(|row| row.len() != width) {
+        return Err(Error::NoSolution(
+            "the rows of the picture have different widths".to_string(),
+        ));
+    }
+
+    let count = (width + 1) / GLYPH_STRIDE;
+    let mut letters = String::new();
+    let mut unknown = Vec::new();
+    for position in 0..count {
+        let start = position * GLYPH_STRIDE;
+        let glyph = rows.iter().map(|row| &row[start..start + GLYPH_WIDTH]);
+        let letter = FONT
+            .iter()
+            .find(|(_, pattern)| glyph.clone().eq(pattern.iter().map(|p| p.as_bytes())))
+            .map(|(letter, _)| *letter);
+        match letter {
+            Some(letter) => letters.push(letter),
+            None => unknown.push((position + 1).to_string()),
+        }
+    }
+
+    if !unknown.is_empty() {
+        return Err(Error::NoSolution(format!(
+            "unrecognised glyphs at positions {}",
+            unknown.join(", ")
+        )));
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn decode_letters() {
+        // GIVEN
+        let picture = indoc! {"
+            ###..#..#.####.#....###..
+            #..#.#..#.#....#....#..#.
+            #..#.####.###..#....#..#.
+            ###..#..#.#....#....###..
+            #....#..#.#....#....#.#..
+            #....#..#.####.####.#..#."};
+
+        // WHEN
+        let letters = decode(picture);
+
+        // THEN
+        assert_eq!(Ok("PHELR".to_string()), letters);
+    }
+
+    #[test]
+    fn decode_unknown_glyphs() {
+        // GIVEN
+        let picture = indoc! {"
+            ##...#..#.####.....
+            ###.......#........
+            ####.#..#.###......
+            ####......#........
+            ####.#..#.#........
+            ####......####....."};
+
+        // WHEN
+        let letters = decode(picture);
+
+        // THEN
+        assert_eq!(
+            "no solution: unrecognised glyphs at positions 1, 2, 4",
+            letters.unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! A small cycle-accurate machine running the handheld device's programs.
+//!
+//! The machine knows nothing about what the device does with its register:
+//! anything interested in its state (signal sampling, drawing on the CRT,
+//! tracing...) is an [`Observer`] notified during every cycle.
+
+use std::fmt::Display;
+
+use common::{Error, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Opcode {
+    Noop,
+    Addx(i32),
+}
+
+impl Opcode {
+    /// Number of cycles the instruction takes to complete.
+    pub fn cycles(&self) -> usize {
+        match self {
+            Opcode::Noop => 1,
+            Opcode::Addx(_) => 2,
+        }
+    }
+}
+
+impl Display for Opcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Opcode::Noop => write!(f, "noop"),
+            Opcode::Addx(n) => write!(f, "addx {n}"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// Line of the instruction in the source, starting at 1
+    pub line: usize,
+    pub opcode: Opcode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    pub fn parse(input: &str) -> Result<Program> {
+        let instructions = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                Ok(Instruction {
+                    line: i + 1,
+                    opcode: Self::parse_opcode(line).map_err(|e| e.within(input, line))?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Program { instructions })
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    fn parse_opcode(line: &str) -> Result<Opcode> {
+        match line.split_once(' ') {
+            None if line == "noop" => Ok(Opcode::Noop),
+            Some(("addx", n)) => n
+                .parse()
+                .map(Opcode::Addx)
+                .map_err(|_| Error::parse(line, n, "an integer operand")),
+            _ => Err(Error::parse(line, line, "`noop` or `addx N`")),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Registers {
+    pub x: i32,
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Registers { x: 1 }
+    }
+}
+
+/// State of the machine during a cycle, before the current instruction completes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tick {
+    /// Number of the cycle, starting at 1
+    pub cycle: usize,
+    pub instruction: Instruction,
+    pub registers: Registers,
+}
+
+pub trait Observer {
+    fn on_cycle(&mut self, tick: &Tick);
+}
+
+pub struct Machine {
+    program: Program,
+    registers: Registers,
+    pc: usize,
+    progress: usize,
+    cycle: usize,
+}
+
+impl Machine {
+    pub fn new(program: Program) -> Self {
+        Machine {
+            program,
+            registers: Registers::default(),
+            pc: 0,
+            progress: 0,
+            cycle: 0,
+        }
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    /// Number of cycles completed so far.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.instructions.len()
+    }
+
+    /// Runs a single cycle, or returns `None` if the program is over.
+    pub fn step(&mut self, observers: &mut [&mut dyn Observer]) -> Result<Option<Tick>> {
+        let Some(&instruction) = self.program.instructions.get(self.pc) else {
+            return Ok(None);
+        };
+        self.cycle += 1;
+        let tick = Tick {
+            cycle: self.cycle,
+            instruction,
+            registers: self.registers,
+        };
+        for observer in observers.iter_mut() {
+            observer.on_cycle(&tick);
+        }
+
+        self.progress += 1;
+        if self.progress == instruction.opcode.cycles() {
+            self.execute(instruction)?;
+            self.pc += 1;
+            self.progress = 0;
+        }
+        Ok(Some(tick))
+    }
+
+    pub fn run(&mut self, observers: &mut [&mut dyn Observer]) -> Result<()> {
+        while self.step(observers)?.is_some() {}
+        Ok(())
+    }
+
+    fn execute(&mut self, instruction: Instruction) -> Result<()> {
+        match instruction.opcode {
+            Opcode::Noop => {}
+            Opcode::Addx(n) => {
+                self.registers.x = self.registers.x.checked_add(n).ok_or_else(|| {
+                    Error::NoSolution(format!(
+                        "line {}: `addx {n}` overflows the X register",
+                        instruction.line
+                    ))
+                })?
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn program_parse_line_numbers() {
+        // GIVEN
+        let input = indoc! {"
+            noop
+            addx -5
+        "};
+
+        // WHEN
+        let program = Program::parse(input).unwrap();
+
+        // THEN
+        assert_eq!(
+            &[
+                Instruction {
+                    line: 1,
+                    opcode: Opcode::Noop
+                },
+                Instruction {
+                    line: 2,
+                    opcode: Opcode::Addx(-5)
+                }
+            ],
+            program.instructions()
+        );
+    }
+
+    #[test]
+    fn machine_step_cycle_by_cycle() {
+        // GIVEN
+        let program = Program::parse("addx 3\nnoop\n").unwrap();
+        let mut machine = Machine::new(program);
+
+        // WHEN
+        let ticks: Vec<_> = std::iter::from_fn(|| machine.step(&mut []).unwrap())
+            .map(|tick| (tick.cycle, tick.instruction.opcode, tick.registers.x))
+            .collect();
+
+        // THEN
+        assert_eq!(
+            vec![
+                (1, Opcode::Addx(3), 1),
+                (2, Opcode::Addx(3), 1),
+                (3, Opcode::Noop, 4)
+            ],
+            ticks
+        );
+        assert!(machine.is_halted());
+        assert_eq!(3, machine.cycle());
+    }
+    #[test]
+    fn machine_x_overflow() {
+        // GIVEN
+        let program = Program::parse("noop\naddx 2147483646\naddx 1\n").unwrap();
+        let mut machine = Machine::new(program);
+
+        // WHEN
+        let result = machine.run(&mut []);
+
+        // THEN
+        assert_eq!(
+            "no solution: line 3: `addx 1` overflows the X register",
+            result.unwrap_err().to_string()
+        );
+        assert_eq!(i32::MAX, machine.registers().x);
+    }
+}
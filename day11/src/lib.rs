@@ -1,23 +1,25 @@
+use common::{Error, Result, Solution};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{digit1, one_of},
-    combinator::map_res,
+    combinator::{consumed, cut, map_res},
     multi::separated_list1,
     sequence::{terminated, tuple},
     IResult,
 };
 
+#[derive(Clone)]
 struct Monkey {
     items: Vec<usize>,
-    operation: Box<dyn Fn(usize) -> usize>,
+    operation: Rc<dyn Fn(usize) -> usize>,
     divisible_by: usize,
     dest_if_true: usize,
     dest_if_false: usize,
     inspect_count: usize,
 }
 
-use std::{collections::BinaryHeap, fmt};
+use std::{collections::BinaryHeap, fmt, rc::Rc};
 impl fmt::Debug for Monkey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Monkey")
@@ -29,18 +31,20 @@ impl fmt::Debug for Monkey {
 
 impl Monkey {
     pub fn from_string(input: &str) -> IResult<&str, Monkey> {
-        let (input, (_, items, operation, (divisible_by, dest_if_true, dest_if_false))) =
+        let (input, (_, (items, operation, (divisible_by, dest_if_true, dest_if_false)))) =
             tuple((
                 Self::monkey_header,
-                Self::monkey_starting_items,
-                Self::monkey_operation,
-                Self::monkey_division_test,
+                cut(tuple((
+                    Self::monkey_starting_items,
+                    Self::monkey_operation,
+                    Self::monkey_division_test,
+                ))),
             ))(input)?;
         Ok((
             input,
             Monkey {
                 items,
-                operation: Box::new(operation),
+                operation: Rc::new(operation),
                 divisible_by,
                 dest_if_true,
                 dest_if_false,
@@ -92,17 +96,43 @@ impl Monkey {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Puzzle {
     monkeys: Vec<Monkey>,
     common_divisor: usize,
 }
 
 impl Puzzle {
-    pub fn from_string(input: &str) -> IResult<&str, Self> {
-        let (input, monkeys) = separated_list1(tag("\n"), Monkey::from_string)(input)?;
+    pub fn from_string(input: &str) -> Result<Self> {
+        let monkeys = common::nom::finish(
+            input,
+            separated_list1(tag("\n"), consumed(Monkey::from_string))(input),
+            "a monkey description",
+        )?;
+
+        for (description, monkey) in &monkeys {
+            if monkey.divisible_by == 0 {
+                return Err(Error::parse(
+                    input,
+                    description,
+                    "a non-zero divisibility test",
+                ));
+            }
+            if monkey.dest_if_true >= monkeys.len() || monkey.dest_if_false >= monkeys.len() {
+                return Err(Error::parse(
+                    input,
+                    description,
+                    format!("throws to monkeys 0 to {}", monkeys.len() - 1),
+                ));
+            }
+        }
+
+        let monkeys: Vec<Monkey> = monkeys.into_iter().map(|(_, monkey)| monkey).collect();
         let common_divisor = monkeys.iter().map(|monkey| monkey.divisible_by).product();
-        Ok((input, Puzzle { monkeys, common_divisor }))
+        Ok(Puzzle {
+            monkeys,
+            common_divisor,
+        })
     }
 
     pub fn monkey_business(&mut self, num_rounds: usize, ridiculous: bool) -> usize {
@@ -125,7 +155,8 @@ impl Puzzle {
             for i in 0..self.monkeys[monkey_index].items.len() {
                 self.monkeys[monkey_index].inspect_count += 1;
                 let worry_level = if ridiculous {
-                    (self.monkeys[monkey_index].operation)(self.monkeys[monkey_index].items[i]) % self.common_divisor
+                    (self.monkeys[monkey_index].operation)(self.monkeys[monkey_index].items[i])
+                        % self.common_divisor
                 } else {
                     (self.monkeys[monkey_index].operation)(self.monkeys[monkey_index].items[i]) / 3
                 };
@@ -134,13 +165,30 @@ impl Puzzle {
                 } else {
                     self.monkeys[monkey_index].dest_if_false
                 };
-                self.monkeys[dest as usize].items.push(worry_level);
+                self.monkeys[dest].items.push(worry_level);
             }
             self.monkeys[monkey_index].items.clear();
         }
     }
 }
 
+impl Solution for Puzzle {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Puzzle::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        Ok(self.clone().monkey_business(20, false))
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        Ok(self.clone().monkey_business(10000, true))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,6 +253,57 @@ mod tests {
         assert_eq!("", remainder);
     }
 
+    #[test]
+    fn parse_puzzle_invalid_operation() {
+        // GIVEN
+        let input = indoc! {"
+            Monkey 0:
+              Starting items: 79, 98
+              Operation: new = old * 19
+              Test: divisible by 23
+                If true: throw to monkey 1
+                If false: throw to monkey 1
+
+            Monkey 1:
+              Starting items: 54, 65, 75, 74
+              Operation: new = old - 6
+              Test: divisible by 19
+                If true: throw to monkey 0
+                If false: throw to monkey 0
+            "};
+
+        // WHEN
+        let result = Puzzle::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 10, column 24: expected a monkey description, found `- 6`",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn parse_puzzle_unknown_target() {
+        // GIVEN
+        let input = indoc! {"
+            Monkey 0:
+              Starting items: 79, 98
+              Operation: new = old * 19
+              Test: divisible by 23
+                If true: throw to monkey 2
+                If false: throw to monkey 0
+            "};
+
+        // WHEN
+        let result = Puzzle::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 1, column 1: expected throws to monkeys 0 to 0, found `Monkey 0:`",
+            result.unwrap_err().to_string()
+        );
+    }
+
     #[test]
     fn monkey_business() {
         // GIVEN
@@ -238,12 +337,11 @@ mod tests {
                 If false: throw to monkey 1
             "};
 
-        let (remainder, mut puzzle) = Puzzle::from_string(input).unwrap();
+        let mut puzzle = Puzzle::from_string(input).unwrap();
         // WHEN
         let result = puzzle.monkey_business(20, false);
 
         // THEN
-        assert_eq!("", remainder);
         assert_eq!(10605, result);
     }
 
@@ -280,12 +378,11 @@ mod tests {
                 If false: throw to monkey 1
             "};
 
-        let (remainder, mut puzzle) = Puzzle::from_string(input).unwrap();
+        let mut puzzle = Puzzle::from_string(input).unwrap();
         // WHEN
         let result = puzzle.monkey_business(10000, true);
 
         // THEN
-        assert_eq!("", remainder);
         assert_eq!(2713310158, result);
     }
 }
@@ -1,15 +1,41 @@
+use common::{Error, Result, Solution};
+use graph::{AdjacencyList, Paths};
+
 pub struct Area {
     grid: Vec<u8>,
     width: usize,
     height: usize,
     start: usize,
     end: usize,
-    min_steps: Vec<usize>,
 }
 
 impl Area {
-    pub fn from_string(input: &str) -> Self {
-        let width = input.chars().position(|x| x == '\n').unwrap();
+    pub fn from_string(input: &str) -> Result<Self> {
+        let width = input.lines().next().map(str::len).unwrap_or_default();
+        if width == 0 {
+            return Err(Error::parse(input, input, "a heightmap"));
+        }
+        for line in input.lines() {
+            if line.len() != width {
+                return Err(Error::parse(
+                    input,
+                    line,
+                    format!("a row of {width} squares"),
+                ));
+            }
+            if let Some((idx, c)) = line
+                .char_indices()
+                .find(|(_, c)| !matches!(c, 'a'..='z' | 'S' | 'E'))
+            {
+                let square = &line[idx..idx + c.len_utf8()];
+                return Err(Error::parse(
+                    input,
+                    square,
+                    "an elevation from a to z, S or E",
+                ));
+            }
+        }
+
         let mut grid: Vec<u8> = input
             .as_bytes()
             .iter()
@@ -17,48 +43,61 @@ impl Area {
             .filter(|c| *c != b'\n')
             .collect();
         let height = grid.len() / width;
-        let start = grid.iter().position(|x| *x == b'S').unwrap();
-        let end = grid.iter().position(|x| *x == b'E').unwrap();
+        let end_of_input = &input[input.len()..];
+        let start = grid
+            .iter()
+            .position(|x| *x == b'S')
+            .ok_or_else(|| Error::parse(input, end_of_input, "a start square S"))?;
+        let end = grid
+            .iter()
+            .position(|x| *x == b'E')
+            .ok_or_else(|| Error::parse(input, end_of_input, "a best signal square E"))?;
         grid[start] = b'a';
         grid[end] = b'z';
-        let mut min_steps = vec![usize::MAX; width * height];
-        min_steps[end] = 0;
-        let mut area = Area {
+        Ok(Area {
             grid,
             width,
             height,
             start,
             end,
-            min_steps,
-        };
-        area.propagate(end);
-        area
+        })
     }
 
-    pub fn min_steps(&mut self) -> usize {
-        self.min_steps[self.start]
+    pub fn min_steps(&self) -> Result<usize> {
+        self.steps_to_end()
+            .distance(self.start)
+            .ok_or_else(|| Error::NoSolution("the start cannot reach the end".to_string()))
     }
 
-    pub fn min_steps_from_all_a(&mut self) -> usize {
+    pub fn min_steps_from_all_a(&self) -> Result<usize> {
+        let steps_to_end = self.steps_to_end();
         self.grid
             .iter()
             .enumerate()
             .filter(|(_, height)| **height == b'a')
             .map(|(i, _)| i)
-            .map(|i| self.min_steps[i])
+            .filter_map(|i| steps_to_end.distance(i))
             .min()
-            .unwrap()
+            .ok_or_else(|| {
+                Error::NoSolution("no square of elevation a can reach the end".to_string())
+            })
     }
 
-    fn propagate(&mut self, i: usize) {
-        for neigh in self.get_neighbors(i).iter().copied() {
-            if self.can_reach(self.grid[neigh], self.grid[i])
-                && self.min_steps[i].saturating_add(1) < self.min_steps[neigh]
-            {
-                self.min_steps[neigh] = self.min_steps[i].saturating_add(1);
-                self.propagate(neigh);
+    fn steps_to_end(&self) -> Paths {
+        self.reversed_moves().bfs(self.end)
+    }
+
+    /// Every possible move, from its destination to its source, to search from the end.
+    fn reversed_moves(&self) -> AdjacencyList {
+        let mut moves = AdjacencyList::new(self.grid.len());
+        for i in 0..self.grid.len() {
+            for neigh in self.get_neighbors(i) {
+                if self.can_reach(self.grid[neigh], self.grid[i]) {
+                    moves.add_edge(i, neigh, 1);
+                }
             }
         }
+        moves
     }
 
     fn can_reach(&self, src: u8, dest: u8) -> bool {
@@ -94,6 +133,23 @@ impl Area {
     }
 }
 
+impl Solution for Area {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Area::from_string(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        self.min_steps()
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        self.min_steps_from_all_a()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,13 +166,53 @@ mod tests {
             abdefghi
             "};
         // WHEN
-        let area = Area::from_string(input);
+        let area = Area::from_string(input).unwrap();
 
         // THEN
         assert_eq!(0, area.start);
         assert_eq!(21, area.end);
     }
 
+    #[test]
+    fn parse_area_without_end() {
+        // GIVEN
+        let input = indoc! {"
+            Sabq
+            abcr
+            "};
+
+        // WHEN
+        let result = Area::from_string(input);
+
+        // THEN
+        assert_eq!(
+            "line 3, column 1: expected a best signal square E, found end of line",
+            result.err().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn min_steps_unreachable() {
+        // GIVEN
+        let input = indoc! {"
+            Sazzz
+            zzzzE
+            "};
+
+        let area = Area::from_string(input).unwrap();
+
+        // WHEN
+        let result = area.min_steps();
+
+        // THEN
+        assert_eq!(
+            Err(Error::NoSolution(
+                "the start cannot reach the end".to_string()
+            )),
+            result
+        );
+    }
+
     #[test]
     fn min_steps() {
         // GIVEN
@@ -128,13 +224,13 @@ mod tests {
             abdefghi
             "};
 
-        let mut area = Area::from_string(input);
+        let area = Area::from_string(input).unwrap();
 
         // WHEN
         let result = area.min_steps();
 
         // THEN
-        assert_eq!(31, result);
+        assert_eq!(Ok(31), result);
     }
 
     #[test]
@@ -148,12 +244,12 @@ mod tests {
             abdefghi
             "};
 
-        let mut area = Area::from_string(input);
+        let area = Area::from_string(input).unwrap();
 
         // WHEN
         let result = area.min_steps_from_all_a();
 
         // THEN
-        assert_eq!(29, result);
+        assert_eq!(Ok(29), result);
     }
 }
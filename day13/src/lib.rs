@@ -1,9 +1,10 @@
 use std::{cmp::Ordering, collections::BTreeSet};
 
+use common::{Result, Solution};
 use nom::{
     branch::alt,
     character::complete::{char, digit1},
-    combinator::map,
+    combinator::{map, map_res},
     multi::{many1, separated_list0},
     sequence::{delimited, terminated, tuple},
     IResult,
@@ -43,7 +44,7 @@ impl Packet {
 
     fn parse(input: &str) -> IResult<&str, Packet> {
         alt((
-            map(digit1, |d: &str| Packet::Num(d.parse().unwrap())),
+            map(map_res(digit1, str::parse), Packet::Num),
             delimited(
                 char('['),
                 map(separated_list0(char(','), Packet::parse), Packet::List),
@@ -51,7 +52,6 @@ impl Packet {
             ),
         ))(input)
     }
-
 }
 
 #[derive(Debug, PartialEq)]
@@ -81,10 +81,14 @@ pub struct PairSignal {
 }
 
 impl PairSignal {
-    pub fn parse(input: &str) -> IResult<&str, PairSignal> {
-        map(separated_list0(char('\n'), Pair::parse), |pairs| {
-            PairSignal { pairs }
-        })(input)
+    pub fn parse(input: &str) -> Result<PairSignal> {
+        common::nom::finish(
+            input,
+            map(separated_list0(char('\n'), Pair::parse), |pairs| {
+                PairSignal { pairs }
+            })(input),
+            "a pair of packets",
+        )
     }
 
     pub fn sum_indices_in_right_order(&self) -> usize {
@@ -101,11 +105,15 @@ pub struct OrderedSignal {
 }
 
 impl OrderedSignal {
-    pub fn parse(input: &str) -> IResult<&str, OrderedSignal> {
-        map(
-            separated_list0(many1(char('\n')), Packet::parse),
-            OrderedSignal::new,
-        )(input)
+    pub fn parse(input: &str) -> Result<OrderedSignal> {
+        common::nom::finish(
+            input,
+            map(
+                separated_list0(many1(char('\n')), Packet::parse),
+                OrderedSignal::new,
+            )(input),
+            "a packet",
+        )
     }
 
     fn new(packets: Vec<Packet>) -> OrderedSignal {
@@ -132,6 +140,31 @@ impl OrderedSignal {
     }
 }
 
+pub struct DistressSignal {
+    pairs: PairSignal,
+    ordered: OrderedSignal,
+}
+
+impl Solution for DistressSignal {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(DistressSignal {
+            pairs: PairSignal::parse(input)?,
+            ordered: OrderedSignal::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        Ok(self.pairs.sum_indices_in_right_order())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        Ok(self.ordered.decoder_key())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,6 +283,27 @@ mod tests {
         assert_eq!(Ordering::Greater, result);
     }
 
+    #[test]
+    fn pair_signal_parse_unbalanced() {
+        // GIVEN
+        let input = indoc! {"
+            [1,1,3,1,1]
+            [1,1,5,1,1]
+
+            [[1],[2,3,4]
+            [[1],4]
+            "};
+
+        // WHEN
+        let result = PairSignal::parse(input);
+
+        // THEN
+        assert_eq!(
+            "line 4, column 1: expected a pair of packets, found `[[1],[2,3,4]`",
+            result.err().unwrap().to_string()
+        );
+    }
+
     #[test]
     fn pair_signal_sum_indices_in_right_order() {
         // GIVEN
@@ -278,7 +332,7 @@ mod tests {
             [1,[2,[3,[4,[5,6,7]]]],8,9]
             [1,[2,[3,[4,[5,6,0]]]],8,9]
             "};
-        let (_, signal) = PairSignal::parse(input).unwrap();
+        let signal = PairSignal::parse(input).unwrap();
 
         // WHEN
         let result = signal.sum_indices_in_right_order();
@@ -315,7 +369,7 @@ mod tests {
             [1,[2,[3,[4,[5,6,7]]]],8,9]
             [1,[2,[3,[4,[5,6,0]]]],8,9]
             "};
-        let (_, signal) = OrderedSignal::parse(input).unwrap();
+        let signal = OrderedSignal::parse(input).unwrap();
 
         // WHEN
         let result = signal.decoder_key();
@@ -0,0 +1,167 @@
+//! Watching the sand fall, one frame at a time.
+
+use std::collections::BTreeSet;
+
+use crate::{Cave, Rules};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Number of simulation steps done before this frame
+    pub step: usize,
+    pub rests: usize,
+    pub picture: String,
+}
+
+/// Iterator over the frames of a simulation, ending with its final state.
+pub struct Animation {
+    cave: Cave,
+    rules: Rules,
+    every: usize,
+    steps: Option<BTreeSet<usize>>,
+    step: usize,
+    pending: bool,
+    finished: bool,
+}
+
+impl Animation {
+    /// Shows a frame every `every` steps.
+    pub fn new(cave: Cave, rules: Rules, every: usize) -> Self {
+        Animation {
+            cave,
+            rules,
+            every: every.max(1),
+            steps: None,
+            step: 0,
+            pending: true,
+            finished: false,
+        }
+    }
+
+    /// Only shows the frames after these numbers of steps, and the final one.
+    pub fn at_steps(cave: Cave, rules: Rules, steps: impl IntoIterator<Item = usize>) -> Self {
+        Animation {
+            steps: Some(steps.into_iter().collect()),
+            ..Animation::new(cave, rules, 1)
+        }
+    }
+
+    pub fn cave(&self) -> &Cave {
+        &self.cave
+    }
+
+    fn is_shown(&self, step: usize) -> bool {
+        match &self.steps {
+            Some(steps) => steps.contains(&step),
+            None => step.is_multiple_of(self.every),
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        Frame {
+            step: self.step,
+            rests: self.cave.number_of_rests(),
+            picture: self.cave.render(&self.rules),
+        }
+    }
+}
+
+impl Iterator for Animation {
+    type Item = Frame;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        while !self.cave.is_done() {
+            if self.pending && self.is_shown(self.step) {
+                self.pending = false;
+                return Some(self.frame());
+            }
+            self.cave.step(&self.rules);
+            self.step += 1;
+            self.pending = true;
+        }
+        self.finished = true;
+        Some(self.frame())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn animation_every_n_steps() {
+        // GIVEN
+        let input = indoc! {"
+            498,4 -> 498,6 -> 496,6
+            503,4 -> 502,4 -> 502,9 -> 494,9
+        "};
+        let cave = Cave::parse(input).unwrap();
+        let animation = Animation::new(cave, Rules::abyss(), 5);
+
+        // WHEN
+        let frames: Vec<_> = animation.take(3).collect();
+
+        // THEN
+        assert_eq!(
+            vec![0, 5, 10],
+            frames.iter().map(|f| f.step).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            indoc! {"
+            .......+....
+            .......o....
+            ............
+            ............
+            .....#...##.
+            .....#...#..
+            ...###...#..
+            .........#..
+            .......o.#..
+            .#########..
+            ............
+            "},
+            frames[2].picture
+        );
+    }
+
+    #[test]
+    fn animation_selected_steps_and_final_frame() {
+        // GIVEN
+        let input = indoc! {"
+            498,4 -> 498,6 -> 496,6
+            503,4 -> 502,4 -> 502,9 -> 494,9
+        "};
+        let cave = Cave::parse(input).unwrap();
+        let animation = Animation::at_steps(cave, Rules::floor(), [1, 3]);
+
+        // WHEN
+        let frames: Vec<_> = animation.collect();
+
+        // THEN
+        assert_eq!(3, frames.len());
+        assert_eq!((1, 0), (frames[0].step, frames[0].rests));
+        assert_eq!((3, 0), (frames[1].step, frames[1].rests));
+        assert_eq!(93, frames[2].rests);
+        assert_eq!(
+            indoc! {"
+            ...........o...........
+            ..........ooo..........
+            .........ooooo.........
+            ........ooooooo........
+            .......oo#ooo##o.......
+            ......ooo#ooo#ooo......
+            .....oo###ooo#oooo.....
+            ....oooo.oooo#ooooo....
+            ...oooooooooo#oooooo...
+            ..ooo#########ooooooo..
+            .ooooo.......ooooooooo.
+            #######################
+            "},
+            frames[2].picture
+        );
+    }
+}
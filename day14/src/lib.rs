@@ -1,30 +1,38 @@
 use std::{
     fmt::Display,
-    ops::{Index, IndexMut},
+    ops::{Index, IndexMut, RangeInclusive},
+    str::FromStr,
 };
 
+use common::{Error, Result, Solution};
 use nom::{
     bytes::complete::tag,
-    character::complete::{char, u32},
-    combinator::map,
+    character::complete::{char, i32},
+    combinator::{consumed, map},
     multi::{many1, separated_list1},
     sequence::{separated_pair, terminated},
     IResult,
 };
 
+pub mod animation;
+pub mod rules;
+pub mod snapshot;
+
+pub use rules::{Rules, Stop};
+
 struct Path {
-    points: Vec<(u32, u32)>,
+    points: Vec<(i32, i32)>,
 }
 
 impl Path {
     fn parse(input: &str) -> IResult<&str, Path> {
         map(
-            separated_list1(tag(" -> "), separated_pair(u32, char(','), u32)),
+            separated_list1(tag(" -> "), separated_pair(i32, char(','), i32)),
             |points| Path { points },
         )(input)
     }
 
-    fn points_in_pair(src: (u32, u32), dest: (u32, u32)) -> Vec<(u32, u32)> {
+    fn points_in_pair(src: (i32, i32), dest: (i32, i32)) -> Vec<(i32, i32)> {
         use std::cmp::{max, min};
 
         if src.0 == dest.0 {
@@ -65,96 +73,274 @@ impl Display for Cell {
     }
 }
 
+/// Area covered by the grid, large enough for every grain of sand to either
+/// rest or fall below the lowest rock.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Bounds {
+    x_min: i32,
+    y_min: i32,
+    width: usize,
+    height: usize,
+}
+
+impl Bounds {
+    /// Most cells the grid may hold, as a cell takes a byte.
+    const MAX_CELLS: i64 = 100_000_000;
+
+    fn from_paths(paths: &[Path], floor: i64) -> Result<Bounds> {
+        let points = || paths.iter().flat_map(|path| path.points.iter());
+        let (source_x, source_y) = (Cave::SOURCE.0 as i64, Cave::SOURCE.1 as i64);
+        // On the floor, sand piles up in a pyramid as wide as it is high
+        let spread = (floor - source_y).max(0);
+
+        let x_min = points()
+            .map(|p| p.0 as i64)
+            .chain([source_x - spread])
+            .min()
+            .unwrap()
+            - 1;
+        let x_max = points()
+            .map(|p| p.0 as i64)
+            .chain([source_x + spread])
+            .max()
+            .unwrap()
+            + 1;
+        let y_min = points()
+            .map(|p| p.1 as i64)
+            .chain([source_y])
+            .min()
+            .unwrap();
+        let y_max = floor.max(source_y);
+
+        let (width, height) = (x_max - x_min + 1, y_max - y_min + 1);
+        let in_range = [x_min, x_max, y_min, y_max]
+            .into_iter()
+            .all(|n| i32::try_from(n).is_ok());
+        if !in_range || width.saturating_mul(height) > Bounds::MAX_CELLS {
+            return Err(Error::InvalidArgument(format!(
+                "the rocks need a grid of {width}x{height} cells, from ({x_min}, {y_min}) \
+                 to ({x_max}, {y_max}), which is too large"
+            )));
+        }
+        Ok(Bounds {
+            x_min: x_min as i32,
+            y_min: y_min as i32,
+            width: width as usize,
+            height: height as usize,
+        })
+    }
+
+    fn contains(&self, (x, y): (i32, i32)) -> bool {
+        (self.x_min..self.x_min + self.width as i32).contains(&x)
+            && (self.y_min..self.y_min + self.height as i32).contains(&y)
+    }
+
+    /// Larger bounds holding `point`, with some room to spare around it.
+    fn including(&self, (x, y): (i32, i32)) -> Bounds {
+        let margin = self.height as i32;
+        let x_min = self.x_min.min(x - margin);
+        let x_max = (self.x_min + self.width as i32 - 1).max(x + margin);
+        let y_min = self.y_min.min(y);
+        let y_max = (self.y_min + self.height as i32 - 1).max(y + margin);
+        Bounds {
+            x_min,
+            y_min,
+            width: (x_max - x_min + 1) as usize,
+            height: (y_max - y_min + 1) as usize,
+        }
+    }
+
+    fn index(&self, (x, y): (i32, i32)) -> usize {
+        (x - self.x_min) as usize + (y - self.y_min) as usize * self.width
+    }
+}
+
+#[derive(Clone)]
 pub struct Cave {
     grid: Vec<Cell>,
-    current_sand: Option<(u32, u32)>,
+    bounds: Bounds,
+    current_sand: Option<(i32, i32)>,
     number_of_rests: usize,
-    abyss_reached: bool,
-    source_reached: bool,
-    floor: u32,
+    stopped: Option<Stop>,
+    next_source: usize,
+    lowest_rock: i32,
+    floor: i32,
 }
 
-impl Index<(u32, u32)> for Cave {
+impl Index<(i32, i32)> for Cave {
     type Output = Cell;
 
-    fn index(&self, (i, j): (u32, u32)) -> &Self::Output {
-        &self.grid[i as usize + j as usize * Cave::WIDTH]
+    fn index(&self, point: (i32, i32)) -> &Self::Output {
+        &self.grid[self.bounds.index(point)]
     }
 }
 
-impl IndexMut<(u32, u32)> for Cave {
-    fn index_mut(&mut self, (i, j): (u32, u32)) -> &mut Self::Output {
-        &mut self.grid[i as usize + j as usize * Cave::WIDTH]
+impl IndexMut<(i32, i32)> for Cave {
+    fn index_mut(&mut self, point: (i32, i32)) -> &mut Self::Output {
+        let index = self.bounds.index(point);
+        &mut self.grid[index]
     }
 }
 
 impl Cave {
-    const WIDTH: usize = 600;
-    const HEIGHT: usize = 500;
+    const SOURCE: (i32, i32) = (500, 0);
+
+    pub fn parse(input: &str) -> Result<Cave> {
+        let paths = common::nom::finish(
+            input,
+            many1(terminated(consumed(Path::parse), char('\n')))(input),
+            "a path of `x,y` points separated by ` -> `",
+        )?;
+
+        for (description, path) in &paths {
+            let diagonal = path
+                .points
+                .windows(2)
+                .any(|window| window[0].0 != window[1].0 && window[0].1 != window[1].1);
+            if diagonal {
+                return Err(Error::parse(
+                    input,
+                    description,
+                    "horizontal or vertical segments",
+                ));
+            }
+        }
 
-    pub fn parse(input: &str) -> IResult<&str, Cave> {
-        map(many1(terminated(Path::parse, char('\n'))), |paths| {
-            Cave::from_paths(&paths)
-        })(input)
+        let paths: Vec<Path> = paths.into_iter().map(|(_, path)| path).collect();
+        Cave::from_paths(&paths)
     }
 
-    fn new() -> Cave {
-        let mut grid = vec![Cell::Air; Cave::WIDTH as usize * Cave::HEIGHT as usize];
-        grid[500] = Cell::Start;
-        Cave {
-            grid,
+    fn from_paths(paths: &[Path]) -> Result<Cave> {
+        let lowest_rock = paths
+            .iter()
+            .flat_map(|path| path.points.iter())
+            .map(|p| p.1)
+            .max()
+            .unwrap_or(Cave::SOURCE.1);
+        let bounds = Bounds::from_paths(paths, lowest_rock as i64 + 2)?;
+        let mut cave = Cave {
+            grid: vec![Cell::Air; bounds.width * bounds.height],
+            bounds,
             current_sand: None,
             number_of_rests: 0,
-            abyss_reached: false,
-            source_reached: false,
-            floor: 0,
-        }
-    }
-
-    fn from_paths(paths: &[Path]) -> Cave {
-        let mut cave = Cave::new();
+            stopped: None,
+            next_source: 0,
+            lowest_rock,
+            floor: lowest_rock + 2,
+        };
+        cave[Cave::SOURCE] = Cell::Start;
         for path in paths {
             for window in path.points.windows(2) {
                 let (src, dest) = (window[0], window[1]);
                 for point in Path::points_in_pair(src, dest) {
                     cave[point] = Cell::Rock;
-                    if point.1 + 2 > cave.floor {
-                        cave.floor = point.1 + 2;
-                    }
                 }
             }
         }
-        cave
+        Ok(cave)
     }
 
     pub fn number_of_rests(&self) -> usize {
         self.number_of_rests
     }
 
-    // ABYSS VERSION
+    pub(crate) fn dump_slice(
+        &self,
+        output: &mut impl std::fmt::Write,
+        x_start: i32,
+        x_end: i32,
+        y_start: i32,
+        y_end: i32,
+    ) {
+        for j in y_start..=y_end {
+            for i in x_start..=x_end {
+                write!(output, "{}", self[(i, j)]).unwrap();
+            }
+            writeln!(output).unwrap();
+        }
+    }
 
-    pub fn step_abyss(&mut self) {
-        match self.current_sand {
-            None => {
-                self.current_sand = Some((500, 1));
-                self[(500, 1)] = Cell::Sand;
+    /// Smallest window holding the source, the rocks and the sand above the floor,
+    /// with a margin of one cell.
+    pub fn window(&self) -> (RangeInclusive<i32>, RangeInclusive<i32>) {
+        let (mut x_min, mut x_max) = (Cave::SOURCE.0, Cave::SOURCE.0);
+        // Rocks may all be above the source, and the floor with them
+        let bottom = self.floor.max(Cave::SOURCE.1 + 1);
+        let rows = (bottom - self.bounds.y_min) as usize;
+        for row in self.grid.chunks(self.bounds.width).take(rows) {
+            for (i, cell) in row.iter().enumerate() {
+                if *cell != Cell::Air {
+                    let x = self.bounds.x_min + i as i32;
+                    x_min = x_min.min(x);
+                    x_max = x_max.max(x);
+                }
             }
+        }
+        let x_start = (x_min - 1).max(self.bounds.x_min);
+        let x_end = (x_max + 1).min(self.bounds.x_min + self.bounds.width as i32 - 1);
+        (x_start..=x_end, self.bounds.y_min..=bottom - 1)
+    }
+
+    /// Draws the window around the active region, with the floor if there is one.
+    pub fn render(&self, rules: &Rules) -> String {
+        let (xs, ys) = self.window();
+        let mut output = String::new();
+        self.dump_slice(&mut output, *xs.start(), *xs.end(), *ys.start(), *ys.end());
+        if rules.floor {
+            output += &"#".repeat(xs.count());
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Moves the falling grain by one cell, or drops a new one.
+    pub fn step(&mut self, rules: &Rules) {
+        if self.stopped.is_some() {
+            return;
+        }
+        match self.current_sand {
+            None => self.spawn(rules),
             Some((s_x, s_y)) => {
-                if !self.try_cell_abyss((s_x, s_y + 1))
-                    && !self.try_cell_abyss((s_x - 1, s_y + 1))
-                    && !self.try_cell_abyss((s_x + 1, s_y + 1))
-                {
+                let moved = rules
+                    .moves
+                    .iter()
+                    .any(|&(dx, dy)| self.try_cell((s_x + dx, s_y + dy), rules.floor));
+                if !moved {
                     self.current_sand = None;
                     self.number_of_rests += 1;
-                }
-                if s_y as usize == Cave::HEIGHT - 2 {
-                    self.abyss_reached = true
+                    if rules.floor && rules.stops_on(Stop::Floor) && s_y == self.floor - 1 {
+                        self.stopped = Some(Stop::Floor);
+                    } else if rules.stops_on(Stop::Grains(self.number_of_rests)) {
+                        self.stopped = Some(Stop::Grains(self.number_of_rests));
+                    }
+                } else if rules.stops_on(Stop::Abyss) && s_y > self.lowest_rock {
+                    self.stopped = Some(Stop::Abyss);
                 }
             }
         }
     }
 
-    fn try_cell_abyss(&mut self, cell: (u32, u32)) -> bool {
+    /// Drops a grain from the next free source, taking them in turn.
+    fn spawn(&mut self, rules: &Rules) {
+        for _ in 0..rules.sources.len() {
+            let source = rules.sources[self.next_source % rules.sources.len()];
+            self.next_source += 1;
+            self.make_room(source);
+            if !self[source].is_blocked() {
+                self.current_sand = Some(source);
+                self[source] = Cell::Sand;
+                return;
+            }
+        }
+        // Nothing can ever move again once every source is blocked
+        self.stopped = Some(Stop::SourceBlocked);
+    }
+
+    fn try_cell(&mut self, cell: (i32, i32), floor: bool) -> bool {
+        if floor && cell.1 >= self.floor {
+            return false;
+        }
+        self.make_room(cell);
         let current_sand = self.current_sand.unwrap();
         if !self[cell].is_blocked() {
             self[current_sand] = Cell::Air;
@@ -166,54 +352,127 @@ impl Cave {
         }
     }
 
-    pub fn step_until_abyss(&mut self) {
-        while !self.abyss_reached {
-            self.step_abyss();
+    /// Grows the grid when rules let the sand go further than the default ones.
+    fn make_room(&mut self, point: (i32, i32)) {
+        if self.bounds.contains(point) {
+            return;
+        }
+        let bounds = self.bounds.including(point);
+        let mut grid = vec![Cell::Air; bounds.width * bounds.height];
+        for (j, row) in self.grid.chunks(self.bounds.width).enumerate() {
+            let y = self.bounds.y_min + j as i32;
+            let start = bounds.index((self.bounds.x_min, y));
+            grid[start..start + self.bounds.width].clone_from_slice(row);
         }
+        self.grid = grid;
+        self.bounds = bounds;
     }
 
-    // FLOOR VERSION
+    pub fn is_done(&self) -> bool {
+        self.stopped.is_some()
+    }
 
-    pub fn step_floor(&mut self) {
-        match self.current_sand {
-            None => {
-                if self[(500, 0)].is_blocked() {
-                    self.source_reached = true;
-                } else {
-                self.current_sand = Some((500, 0));
-                self[(500, 0)] = Cell::Sand;
-                }
-            }
-            Some((s_x, s_y)) => {
-                if !self.try_cell_floor((s_x, s_y + 1))
-                    && !self.try_cell_floor((s_x - 1, s_y + 1))
-                    && !self.try_cell_floor((s_x + 1, s_y + 1))
-                {
-                    self.current_sand = None;
-                    self.number_of_rests += 1;
-                }
+    pub fn stopped(&self) -> Option<Stop> {
+        self.stopped
+    }
+
+    /// Steps until the rules stop the simulation, and tells why.
+    pub fn run(&mut self, rules: &Rules) -> Stop {
+        loop {
+            if let Some(stop) = self.stopped {
+                return stop;
             }
+            self.step(rules);
         }
     }
 
-    fn try_cell_floor(&mut self, cell: (u32, u32)) -> bool {
-        let current_sand = self.current_sand.unwrap();
-        if cell.1 != self.floor && !self[cell].is_blocked() {
-            self[current_sand] = Cell::Air;
-            self[cell] = Cell::Sand;
-            self.current_sand = Some(cell);
-            true
-        } else {
-            false
-        }
+    pub fn step_until_abyss(&mut self) {
+        self.run(&Rules::abyss());
     }
 
     pub fn step_until_source_blocked(&mut self) {
-        while !self.source_reached {
-            self.step_floor();
+        self.run(&Rules::floor());
+    }
+
+    /// Number of grains resting on the floor once the source is blocked with the
+    /// default rules, without simulating them: every cell reachable from the source by moving down,
+    /// down-left or down-right ends up filled with sand.
+    pub fn rests_on_floor_by_flood(&self) -> usize {
+        let width = self.bounds.width;
+        let is_rock = |i: usize, y: i32| self[(self.bounds.x_min + i as i32, y)] == Cell::Rock;
+
+        let mut row = vec![false; width];
+        let (source_x, source_y) = Cave::SOURCE;
+        let source = (source_x - self.bounds.x_min) as usize;
+        row[source] = !is_rock(source, source_y);
+        let mut count = row[source] as usize;
+
+        for y in source_y + 1..self.floor {
+            row = (0..width)
+                .map(|i| {
+                    let above = row[i.saturating_sub(1)..=(i + 1).min(width - 1)]
+                        .iter()
+                        .any(|&reachable| reachable);
+                    above && !is_rock(i, y)
+                })
+                .collect();
+            count += row.iter().filter(|&&reachable| reachable).count();
         }
+        count
     }
 
+    pub fn rests_on_floor(&self, solver: FloorSolver) -> usize {
+        match solver {
+            FloorSolver::Simulation => {
+                let mut cave = self.clone();
+                cave.step_until_source_blocked();
+                cave.number_of_rests()
+            }
+            FloorSolver::Flood => self.rests_on_floor_by_flood(),
+        }
+    }
+}
+
+/// How to count the grains resting on the floor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FloorSolver {
+    /// Drop every grain, one cell at a time
+    Simulation,
+    /// Count the cells reachable from the source, row by row
+    Flood,
+}
+
+impl FromStr for FloorSolver {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "simulation" => Ok(FloorSolver::Simulation),
+            "flood" => Ok(FloorSolver::Flood),
+            _ => Err(Error::InvalidArgument(format!(
+                "expected `simulation` or `flood`, found `{s}`"
+            ))),
+        }
+    }
+}
+
+impl Solution for Cave {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Cave::parse(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        let mut cave = self.clone();
+        cave.step_until_abyss();
+        Ok(cave.number_of_rests())
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        Ok(self.rests_on_floor(FloorSolver::Flood))
+    }
 }
 
 #[cfg(test)]
@@ -223,26 +482,10 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     impl Cave {
-        fn dump_slice(
-            &self,
-            output: &mut impl std::fmt::Write,
-            x_start: u32,
-            x_end: u32,
-            y_start: u32,
-            y_end: u32,
-        ) {
-            for j in y_start..=y_end {
-                for i in x_start..=x_end {
-                    write!(output, "{}", self[(i, j)]).unwrap();
-                }
-                writeln!(output).unwrap();
-            }
-        }
-
         fn step_until_rest(&mut self) {
-            self.step_abyss();
+            self.step(&Rules::abyss());
             while self.current_sand.is_some() {
-                self.step_abyss();
+                self.step(&Rules::abyss());
             }
         }
 
@@ -253,7 +496,6 @@ mod tests {
         }
     }
 
-
     #[test]
     fn cave_dump_slice() {
         // GIVEN
@@ -261,7 +503,7 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let (_, cave) = Cave::parse(input).unwrap();
+        let cave = Cave::parse(input).unwrap();
 
         // WHEN
         let mut output = String::new();
@@ -286,6 +528,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cave_parse_diagonal_path() {
+        // GIVEN
+        let input = indoc! {"
+            498,4 -> 498,6 -> 496,6
+            503,4 -> 502,5
+        "};
+
+        // WHEN
+        let result = Cave::parse(input);
+
+        // THEN
+        assert_eq!(
+            "line 2, column 1: expected horizontal or vertical segments, found `503,4 -> 502,5`",
+            result.err().unwrap().to_string()
+        );
+    }
+
     #[test]
     fn cave_one_step() {
         // GIVEN
@@ -293,10 +553,10 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let (_, mut cave) = Cave::parse(input).unwrap();
+        let mut cave = Cave::parse(input).unwrap();
 
         // WHEN
-        cave.step_abyss();
+        cave.step(&Rules::abyss());
 
         // THEN
         let mut output = String::new();
@@ -326,11 +586,11 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let (_, mut cave) = Cave::parse(input).unwrap();
+        let mut cave = Cave::parse(input).unwrap();
 
         // WHEN
-        cave.step_abyss();
-        cave.step_abyss();
+        cave.step(&Rules::abyss());
+        cave.step(&Rules::abyss());
 
         // THEN
         let mut output = String::new();
@@ -360,7 +620,7 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let (_, mut cave) = Cave::parse(input).unwrap();
+        let mut cave = Cave::parse(input).unwrap();
 
         // WHEN
         cave.step_until_rest();
@@ -393,7 +653,7 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let (_, mut cave) = Cave::parse(input).unwrap();
+        let mut cave = Cave::parse(input).unwrap();
 
         // WHEN
         cave.step_until_n_rests(5);
@@ -426,7 +686,7 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let (_, mut cave) = Cave::parse(input).unwrap();
+        let mut cave = Cave::parse(input).unwrap();
 
         // WHEN
         cave.step_until_abyss();
@@ -444,7 +704,7 @@ mod tests {
     "};
 
     // WHEN
-    let (_, cave) = Cave::parse(input).unwrap();
+    let cave = Cave::parse(input).unwrap();
 
     // THEN
     assert_eq!(11, cave.floor);
@@ -457,7 +717,7 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let (_, mut cave) = Cave::parse(input).unwrap();
+        let mut cave = Cave::parse(input).unwrap();
 
         // WHEN
         cave.step_until_source_blocked();
@@ -465,4 +725,105 @@ mod tests {
         // THEN
         assert_eq!(93, cave.number_of_rests());
     }
+
+    #[test]
+    fn cave_negative_and_wide_bounds() {
+        // GIVEN
+        let input = indoc! {"
+            -10,150 -> -8,150
+        "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let mut abyss = cave.clone();
+        abyss.step_until_abyss();
+        let mut floor = cave;
+        floor.step_until_source_blocked();
+
+        // THEN
+        assert_eq!(0, abyss.number_of_rests());
+        assert_eq!(152 * 152, floor.number_of_rests());
+    }
+
+    #[test]
+    fn cave_rocks_above_source() {
+        // GIVEN
+        let input = indoc! {"
+            498,-5 -> 502,-5
+        "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let mut abyss = cave.clone();
+        abyss.step_until_abyss();
+        let mut floor = cave.clone();
+        floor.step_until_source_blocked();
+
+        // THEN
+        assert_eq!(0, abyss.number_of_rests());
+        assert_eq!(1, floor.number_of_rests());
+        assert_eq!(1, cave.rests_on_floor(FloorSolver::Flood));
+        assert_eq!((497..=503, -5..=0), cave.window());
+    }
+
+    #[test]
+    fn cave_rocks_too_far() {
+        // GIVEN
+        let inputs = [
+            "500,100000 -> 501,100000\n",
+            "500,2147483647 -> 501,2147483647\n",
+        ];
+
+        // WHEN
+        let errors: Vec<_> = inputs
+            .iter()
+            .map(|input| Cave::parse(input).err().unwrap().to_string())
+            .collect();
+
+        // THEN
+        assert_eq!(
+            vec![
+                "invalid argument: the rocks need a grid of 200007x100003 cells, \
+                 from (-99503, 0) to (100503, 100002), which is too large",
+                "invalid argument: the rocks need a grid of 4294967301x2147483650 cells, \
+                 from (-2147483150, 0) to (2147484150, 2147483649), which is too large",
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn cave_flood_matches_simulation() {
+        // GIVEN
+        let inputs = [
+            indoc! {"
+                498,4 -> 498,6 -> 496,6
+                503,4 -> 502,4 -> 502,9 -> 494,9
+            "},
+            indoc! {"
+                -10,150 -> -8,150
+            "},
+            indoc! {"
+                500,2 -> 500,2
+                490,5 -> 510,5
+                495,9 -> 495,7 -> 505,7 -> 505,9
+                480,14 -> 520,14
+            "},
+            indoc! {"
+                499,1 -> 501,1
+                500,3 -> 500,3
+            "},
+        ];
+
+        for input in inputs {
+            let cave = Cave::parse(input).unwrap();
+
+            // WHEN
+            let simulated = cave.rests_on_floor(FloorSolver::Simulation);
+            let flooded = cave.rests_on_floor(FloorSolver::Flood);
+
+            // THEN
+            assert_eq!(simulated, flooded, "for {input}");
+        }
+    }
 }
@@ -0,0 +1,208 @@
+//! How the sand moves, and when the simulation stops.
+
+use common::{Error, Result};
+
+use crate::Cave;
+
+/// Why a simulation stopped.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stop {
+    /// A grain fell below the lowest rock
+    Abyss,
+    /// A grain came to rest on the floor
+    Floor,
+    /// Every source is covered with sand
+    SourceBlocked,
+    /// This many grains came to rest
+    Grains(usize),
+}
+
+/// Physics of the falling sand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Offsets tried in order by a falling grain, which rests when none is free
+    pub(crate) moves: Vec<(i32, i32)>,
+    /// Cells where new grains appear, taken in turn
+    pub(crate) sources: Vec<(i32, i32)>,
+    /// Whether there is a floor two units below the lowest rock
+    pub(crate) floor: bool,
+    /// The simulation stops as soon as one of these happens
+    pub(crate) stops: Vec<Stop>,
+}
+
+impl Rules {
+    const MOVES: [(i32, i32); 3] = [(0, 1), (-1, 1), (1, 1)];
+
+    /// Part 1: the sand flows into the abyss.
+    pub fn abyss() -> Self {
+        let (x, y) = Cave::SOURCE;
+        Rules {
+            moves: Rules::MOVES.to_vec(),
+            // Grains appear right below the source
+            sources: vec![(x, y + 1)],
+            floor: false,
+            stops: vec![Stop::Abyss],
+        }
+    }
+
+    /// Part 2: the sand piles up on the floor until it blocks the source.
+    pub fn floor() -> Self {
+        Rules {
+            moves: Rules::MOVES.to_vec(),
+            sources: vec![Cave::SOURCE],
+            floor: true,
+            stops: vec![Stop::SourceBlocked],
+        }
+    }
+
+    /// Custom physics, which must end every simulation: grains always move down, and
+    /// either land on the floor or stop it when they fall into the abyss.
+    pub fn new(
+        moves: Vec<(i32, i32)>,
+        sources: Vec<(i32, i32)>,
+        floor: bool,
+        stops: Vec<Stop>,
+    ) -> Result<Self> {
+        if moves.iter().any(|&(_, dy)| dy <= 0) {
+            return Err(Error::InvalidArgument(
+                "every move must go down, or the sand may never rest".to_string(),
+            ));
+        }
+        if !floor && !stops.contains(&Stop::Abyss) {
+            return Err(Error::InvalidArgument(
+                "without a floor, the simulation must stop on the abyss".to_string(),
+            ));
+        }
+        Ok(Rules {
+            moves,
+            sources,
+            floor,
+            stops,
+        })
+    }
+
+    /// Also stops once `grains` grains came to rest.
+    pub fn with_max_grains(mut self, grains: usize) -> Self {
+        self.stops.push(Stop::Grains(grains));
+        self
+    }
+
+    pub fn stops_on(&self, stop: Stop) -> bool {
+        self.stops.contains(&stop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rules_presets() {
+        // GIVEN
+        let input = indoc! {"
+            498,4 -> 498,6 -> 496,6
+            503,4 -> 502,4 -> 502,9 -> 494,9
+        "};
+        let mut abyss = Cave::parse(input).unwrap();
+        let mut floor = abyss.clone();
+
+        // WHEN
+        let stops = (abyss.run(&Rules::abyss()), floor.run(&Rules::floor()));
+
+        // THEN
+        assert_eq!((Stop::Abyss, Stop::SourceBlocked), stops);
+        assert_eq!((24, 93), (abyss.number_of_rests(), floor.number_of_rests()));
+    }
+
+    #[test]
+    fn rules_max_grains() {
+        // GIVEN
+        let input = indoc! {"
+            498,4 -> 498,6 -> 496,6
+            503,4 -> 502,4 -> 502,9 -> 494,9
+        "};
+        let mut cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let stop = cave.run(&Rules::floor().with_max_grains(10));
+
+        // THEN
+        assert_eq!(Stop::Grains(10), stop);
+        assert_eq!(10, cave.number_of_rests());
+    }
+
+    #[test]
+    fn rules_first_grain_on_floor() {
+        // GIVEN
+        let input = indoc! {"
+            498,4 -> 498,6 -> 496,6
+            503,4 -> 502,4 -> 502,9 -> 494,9
+        "};
+        let mut cave = Cave::parse(input).unwrap();
+        let rules = Rules {
+            stops: vec![Stop::Floor],
+            ..Rules::floor()
+        };
+
+        // WHEN
+        let stop = cave.run(&rules);
+
+        // THEN
+        assert_eq!(Stop::Floor, stop);
+        assert_eq!(25, cave.number_of_rests());
+    }
+
+    #[test]
+    fn rules_wide_fall_and_two_sources() {
+        // GIVEN
+        let mut cave = Cave::parse("500,3 -> 500,3\n").unwrap();
+        let rules = Rules {
+            moves: vec![(0, 1), (-2, 1), (2, 1)],
+            sources: vec![(500, 0), (504, 0)],
+            ..Rules::floor()
+        };
+
+        // WHEN
+        cave.run(&rules.with_max_grains(6));
+
+        // THEN
+        let mut output = String::new();
+        cave.dump_slice(&mut output, 496, 506, 0, 4);
+        assert_eq!(
+            indoc! {"
+            ...........
+            ...........
+            ...........
+            ....#......
+            o.o.o.o.o.o
+            "},
+            output
+        );
+    }
+
+    #[test]
+    fn rules_that_never_stop() {
+        // GIVEN
+        let sideways = Rules::new(vec![(0, 1), (1, 0)], vec![(500, 0)], true, vec![]);
+        let bottomless = Rules::new(
+            Rules::MOVES.to_vec(),
+            vec![(500, 0)],
+            false,
+            vec![Stop::Grains(10)],
+        );
+
+        // WHEN
+        let errors = [sideways, bottomless].map(|rules| rules.unwrap_err().to_string());
+
+        // THEN
+        assert_eq!(
+            [
+                "invalid argument: every move must go down, or the sand may never rest",
+                "invalid argument: without a floor, the simulation must stop on the abyss",
+            ],
+            errors
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Saving a cave to text and reading it back, to resume a simulation or draw fixtures.
+//!
+//! A snapshot starts with a few header lines, then a blank line and the whole grid
+//! in the puzzle's `.#o+` notation:
+//!
+//! ```text
+//! origin 493,0
+//! floor 11
+//! rests 1
+//! sand 500,3
+//!
+//! .......+....
+//! ............
+//! .......o....
+//! ```
+//!
+//! `floor` defaults to two rows below the lowest rock, `rests` to 0, and `sand`,
+//! the grain still falling, is only given when there is one.
+
+use std::fmt::Write;
+
+use common::{Error, Result};
+use nom::{
+    bytes::complete::{is_a, tag},
+    character::complete::{char, i32, u64},
+    combinator::opt,
+    multi::many1,
+    sequence::{delimited, separated_pair, terminated, tuple},
+    IResult,
+};
+
+use crate::{Bounds, Cave, Cell};
+
+struct Header {
+    origin: (i32, i32),
+    floor: Option<i32>,
+    rests: Option<u64>,
+    sand: Option<(i32, i32)>,
+}
+
+fn point(input: &str) -> IResult<&str, (i32, i32)> {
+    separated_pair(i32, char(','), i32)(input)
+}
+
+fn header(input: &str) -> IResult<&str, Header> {
+    let (input, (origin, floor, rests, sand)) = tuple((
+        delimited(tag("origin "), point, char('\n')),
+        opt(delimited(tag("floor "), i32, char('\n'))),
+        opt(delimited(tag("rests "), u64, char('\n'))),
+        opt(delimited(tag("sand "), point, char('\n'))),
+    ))(input)?;
+    Ok((
+        input,
+        Header {
+            origin,
+            floor,
+            rests,
+            sand,
+        },
+    ))
+}
+
+fn snapshot(input: &str) -> IResult<&str, (Header, Vec<&str>)> {
+    separated_pair(
+        header,
+        char('\n'),
+        many1(terminated(is_a(".#o+"), char('\n'))),
+    )(input)
+}
+
+impl Cave {
+    /// The whole state of the cave, readable by [`Cave::from_snapshot`].
+    pub fn snapshot(&self) -> String {
+        let mut output = String::new();
+        writeln!(output, "origin {},{}", self.bounds.x_min, self.bounds.y_min).unwrap();
+        writeln!(output, "floor {}", self.floor).unwrap();
+        writeln!(output, "rests {}", self.number_of_rests).unwrap();
+        if let Some((x, y)) = self.current_sand {
+            writeln!(output, "sand {x},{y}").unwrap();
+        }
+        output.push('\n');
+        let x_end = self.bounds.x_min + self.bounds.width as i32 - 1;
+        let y_end = self.bounds.y_min + self.bounds.height as i32 - 1;
+        self.dump_slice(
+            &mut output,
+            self.bounds.x_min,
+            x_end,
+            self.bounds.y_min,
+            y_end,
+        );
+        output
+    }
+
+    pub fn from_snapshot(input: &str) -> Result<Cave> {
+        let (header, rows) = common::nom::finish(
+            input,
+            snapshot(input),
+            "an `origin x,y` line, optional `floor`, `rests` and `sand` lines, \
+             a blank line and rows of `.#o+` cells",
+        )?;
+
+        let width = rows[0].len();
+        if let Some(row) = rows.iter().find(|row| row.len() != width) {
+            return Err(Error::parse(input, row, format!("{width} cells")));
+        }
+        let bounds = Bounds {
+            x_min: header.origin.0,
+            y_min: header.origin.1,
+            width,
+            height: rows.len(),
+        };
+        let grid: Vec<Cell> = rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .map(|c| match c {
+                '#' => Cell::Rock,
+                'o' => Cell::Sand,
+                '+' => Cell::Start,
+                _ => Cell::Air,
+            })
+            .collect();
+
+        let lowest_rock = grid
+            .chunks(width)
+            .rposition(|row| row.contains(&Cell::Rock))
+            .map(|j| bounds.y_min + j as i32)
+            .unwrap_or(Cave::SOURCE.1);
+        let floor = header.floor.unwrap_or(lowest_rock + 2);
+        let mut cave = Cave {
+            grid,
+            bounds,
+            current_sand: None,
+            number_of_rests: header.rests.unwrap_or(0) as usize,
+            stopped: None,
+            next_source: 0,
+            lowest_rock: floor - 2,
+            floor,
+        };
+
+        if let Some(sand) = header.sand {
+            if !cave.bounds.contains(sand) || cave[sand] != Cell::Sand {
+                return Err(Error::parse(
+                    input,
+                    input
+                        .lines()
+                        .find(|line| line.starts_with("sand "))
+                        .unwrap(),
+                    "the position of an `o` cell",
+                ));
+            }
+            cave.current_sand = Some(sand);
+        }
+
+        // Room for the pyramid the floor rules build under the source
+        let spread = floor - Cave::SOURCE.1;
+        cave.make_room((Cave::SOURCE.0 - spread, floor));
+        cave.make_room((Cave::SOURCE.0 + spread, floor));
+        Ok(cave)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rules, Stop};
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn snapshot_round_trip() {
+        // GIVEN
+        let input = indoc! {"
+            498,4 -> 498,6 -> 496,6
+            503,4 -> 502,4 -> 502,9 -> 494,9
+        "};
+        let mut cave = Cave::parse(input).unwrap();
+        for _ in 0..300 {
+            cave.step(&Rules::floor());
+        }
+
+        // WHEN
+        let mut resumed = Cave::from_snapshot(&cave.snapshot()).unwrap();
+
+        // THEN
+        assert_eq!(cave.snapshot(), resumed.snapshot());
+        cave.step_until_source_blocked();
+        resumed.step_until_source_blocked();
+        assert_eq!(cave.snapshot(), resumed.snapshot());
+        assert_eq!(93, resumed.number_of_rests());
+    }
+
+    #[test]
+    fn snapshot_drawn_fixture() {
+        // GIVEN
+        let input = indoc! {"
+            origin 497,0
+            rests 4
+
+            ...+...
+            .......
+            .......
+            .#.o.#.
+            .#ooo#.
+            .#####.
+        "};
+
+        // WHEN
+        let mut cave = Cave::from_snapshot(input).unwrap();
+        let stop = cave.run(&Rules::abyss());
+
+        // THEN
+        assert_eq!((Stop::SourceBlocked, 7), (stop, cave.floor));
+        let mut output = String::new();
+        cave.dump_slice(&mut output, 497, 503, 0, 5);
+        assert_eq!(
+            indoc! {"
+            ...+...
+            ...o...
+            ..ooo..
+            .#ooo#.
+            .#ooo#.
+            .#####.
+            "},
+            output
+        );
+        assert_eq!(10, cave.number_of_rests());
+    }
+
+    #[test]
+    fn snapshot_ragged_rows() {
+        // GIVEN
+        let input = indoc! {"
+            origin 499,0
+
+            .+.
+            ..
+        "};
+
+        // WHEN
+        let result = Cave::from_snapshot(input);
+
+        // THEN
+        assert_eq!(
+            "line 4, column 1: expected 3 cells, found `..`",
+            result.err().unwrap().to_string()
+        );
+    }
+}
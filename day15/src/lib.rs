@@ -1,12 +1,16 @@
-#![feature(iterator_try_reduce)]
-#![feature(control_flow_enum)]
-
+use common::{Error, Result, Solution};
 use rayon::prelude::*;
 
-use std::{collections::HashSet, ops::ControlFlow};
+use std::collections::HashSet;
 
 use regex::Regex;
 
+pub mod map;
+pub mod search;
+
+pub use map::MapFormat;
+pub use search::BeaconSearch;
+
 #[derive(Debug, Eq, PartialEq, Hash)]
 struct Point {
     x: i64,
@@ -26,125 +30,222 @@ struct ClampedRange {
 }
 
 impl ClampedRange {
-    fn new(start: i64, end: i64, lower_bound: i64, higher_bound: i64) -> Self {
-        ClampedRange {
+    /// The part of `start..=end` within the bounds, if any.
+    fn new(start: i64, end: i64, lower_bound: i64, higher_bound: i64) -> Option<Self> {
+        if end < lower_bound || start > higher_bound {
+            return None;
+        }
+        Some(ClampedRange {
             start: num::clamp(start, lower_bound, higher_bound),
             end: num::clamp(end, lower_bound, higher_bound),
-        }
+        })
     }
 
     fn maybe_merge(&self, other: ClampedRange) -> Option<ClampedRange> {
-        if self.end < other.start {
+        if self.end + 1 < other.start {
             None
         } else {
-            Some(ClampedRange { start: self.start, end: std::cmp::max(self.end, other.end) })
+            Some(ClampedRange {
+                start: self.start,
+                end: std::cmp::max(self.end, other.end),
+            })
         }
+    }
 
+    fn contains(&self, x: i64) -> bool {
+        self.start <= x && x <= self.end
     }
 
+    fn len(&self) -> usize {
+        (self.end - self.start + 1) as usize
+    }
+}
+
+/// Where to look, and how to report what was found.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The distress beacon has coordinates between 0 and these bounds
+    pub x_bound: i64,
+    pub y_bound: i64,
+    /// Row whose positions without a beacon are counted in part 1
+    pub row: i64,
+    /// Factor applied to x in the tuning frequency
+    pub multiplier: i64,
 }
 
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            x_bound: 4000000,
+            y_bound: 4000000,
+            row: 2000000,
+            multiplier: 4000000,
+        }
+    }
+}
+
+impl Config {
+    /// The smaller area of the example in the puzzle text.
+    pub fn example() -> Self {
+        Config {
+            x_bound: 20,
+            y_bound: 20,
+            row: 10,
+            ..Config::default()
+        }
+    }
+}
 
 pub struct Tunnel {
     pairs: Vec<(Point, Point)>,
     x_bound: i64,
     y_bound: i64,
+    row: i64,
+    multiplier: i64,
 }
 
 impl Tunnel {
-    pub fn parse(input: &str, x_bound: i64, y_bound: i64) -> Tunnel {
+    pub fn parse(input: &str, config: Config) -> Result<Tunnel> {
         let re = Regex::new(
             r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)",
         )
         .unwrap();
-        Tunnel {
-            pairs: re
-                .captures_iter(input)
-                .map(|cap| {
-                    (
-                        Point {
-                            x: cap[1].parse().unwrap(),
-                            y: cap[2].parse().unwrap(),
-                        },
-                        Point {
-                            x: cap[3].parse().unwrap(),
-                            y: cap[4].parse().unwrap(),
-                        },
+        let pairs = input
+            .lines()
+            .map(|line| {
+                let cap = re.captures(line).ok_or_else(|| {
+                    Error::parse(
+                        input,
+                        line,
+                        "`Sensor at x=N, y=N: closest beacon is at x=N, y=N`",
                     )
-                })
-                .collect(),
-            x_bound,
-            y_bound,
-        }
+                })?;
+                let coordinate = |i: usize| {
+                    let group = cap.get(i).map(|m| m.as_str()).unwrap_or_default();
+                    group
+                        .parse()
+                        .map_err(|_| Error::parse(input, group, "a 64-bit coordinate"))
+                };
+                Ok((
+                    Point {
+                        x: coordinate(1)?,
+                        y: coordinate(2)?,
+                    },
+                    Point {
+                        x: coordinate(3)?,
+                        y: coordinate(4)?,
+                    },
+                ))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Tunnel {
+            pairs,
+            x_bound: config.x_bound,
+            y_bound: config.y_bound,
+            row: config.row,
+            multiplier: config.multiplier,
+        })
     }
 
     pub fn number_of_not_beacons_on_line(&self, y: i64) -> usize {
-        self.not_beacons_on_line(y)
+        let ranges = self.covered_on_line(y, i64::MIN, i64::MAX);
+        let covered: usize = ranges.iter().map(ClampedRange::len).sum();
+        let beacons: HashSet<i64> = self
+            .pairs
             .iter()
-            .filter(|p| self.pairs.iter().all(|(s, b)| *p != s && *p != b))
-            .count()
-    }
-
-    pub fn tuning_frequency(&self) -> i64 {
-        let beacon = self.find_beacon();
-        beacon.x * 4000000 + beacon.y
-    }
-
-
-    fn find_beacon(&self) -> Point {
-        (0..=self.y_bound)
-            .into_par_iter()
-            .find_map_first(|y| {
-                let mut ranges = Vec::new();
-
-                for (sensor, beacon) in self.pairs.iter() {
-                    let dist_to_line = sensor.y.abs_diff(y);
-                    let range_radius = sensor.distance(beacon).saturating_sub(dist_to_line);
-
-                    let range = ClampedRange::new(
-                        sensor.x - range_radius as i64,
-                        sensor.x + range_radius as i64,
-                        0,
-                        self.x_bound,
-                    );
-                    ranges.push(range);
-                }
-                ranges.sort_unstable();
-                ranges
-                    .into_iter()
-                    .try_reduce(|acc, range| {
-                        if let Some(new_range) = acc.maybe_merge(range) {
-                            ControlFlow::Continue(new_range)
-                        } else {
-                            ControlFlow::Break(acc)
-                        }
-                    })
-                    .break_value()
-                    .map(|range| Point {
-                        x: range.end + 1,
-                        y,
-                    })
+            .map(|(_, beacon)| beacon)
+            .filter(|beacon| beacon.y == y && ranges.iter().any(|r| r.contains(beacon.x)))
+            .map(|beacon| beacon.x)
+            .collect();
+        covered - beacons.len()
+    }
+
+    pub fn tuning_frequency(&self) -> Result<i64> {
+        self.tuning_frequency_with(BeaconSearch::Edges)
+    }
+
+    pub fn tuning_frequency_with(&self, search: BeaconSearch) -> Result<i64> {
+        let beacon = self.find_beacon_with(search).ok_or_else(|| {
+            Error::NoSolution("every position in bounds is covered by a sensor".to_string())
+        })?;
+        beacon
+            .x
+            .checked_mul(self.multiplier)
+            .and_then(|frequency| frequency.checked_add(beacon.y))
+            .ok_or_else(|| {
+                Error::InvalidArgument(format!(
+                    "the tuning frequency overflows with a multiplier of {}",
+                    self.multiplier
+                ))
             })
-            .unwrap()
     }
 
-    fn not_beacons_on_line(&self, y: i64) -> HashSet<Point> {
-        let mut set = HashSet::new();
+    fn find_beacon_with(&self, search: BeaconSearch) -> Option<Point> {
+        match search {
+            BeaconSearch::RowScan => self.find_beacon_by_row_scan(),
+            BeaconSearch::Edges => self.find_beacon_by_edges(),
+        }
+    }
 
-        for (sensor, beacon) in self.pairs.iter() {
-            let dist_to_line = sensor.y.abs_diff(y);
-            let range = sensor.distance(beacon).saturating_sub(dist_to_line) as i64;
+    fn find_beacon_by_row_scan(&self) -> Option<Point> {
+        (0..=self.y_bound).into_par_iter().find_map_first(|y| {
+            let ranges = self.covered_on_line(y, 0, self.x_bound);
+            let x = match ranges.first() {
+                None => 0,
+                Some(first) if first.start > 0 => 0,
+                Some(first) if first.end < self.x_bound => first.end + 1,
+                Some(_) => return None,
+            };
+            Some(Point { x, y })
+        })
+    }
 
-            for offset in -range..=range {
-                let candidate = Point {
-                    x: sensor.x + offset,
-                    y,
-                };
-                set.insert(candidate);
+    /// Sorted and disjoint ranges of x covered by the sensors on line `y`, within bounds.
+    fn covered_on_line(&self, y: i64, lower_bound: i64, higher_bound: i64) -> Vec<ClampedRange> {
+        let mut ranges: Vec<_> = self
+            .pairs
+            .iter()
+            .filter_map(|(sensor, beacon)| {
+                let dist_to_line = sensor.y.abs_diff(y);
+                let range_radius = sensor.distance(beacon).checked_sub(dist_to_line)? as i64;
+                ClampedRange::new(
+                    sensor.x - range_radius,
+                    sensor.x + range_radius,
+                    lower_bound,
+                    higher_bound,
+                )
+            })
+            .collect();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<ClampedRange> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) => match last.maybe_merge(range) {
+                    Some(union) => *last = union,
+                    None => merged.push(range),
+                },
+                None => merged.push(range),
             }
         }
+        merged
+    }
+}
 
-        set
+impl Solution for Tunnel {
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Tunnel::parse(input, Config::default())
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        Ok(self.number_of_not_beacons_on_line(self.row))
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        self.tuning_frequency()
     }
 }
 
@@ -153,6 +254,24 @@ mod tests {
     use super::*;
     use indoc::indoc;
 
+    /// The puzzle's example, for the tests that are not about reading it.
+    pub(crate) const EXAMPLE: &str = indoc! {"
+        Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        Sensor at x=9, y=16: closest beacon is at x=10, y=16
+        Sensor at x=13, y=2: closest beacon is at x=15, y=3
+        Sensor at x=12, y=14: closest beacon is at x=10, y=16
+        Sensor at x=10, y=20: closest beacon is at x=10, y=16
+        Sensor at x=14, y=17: closest beacon is at x=10, y=16
+        Sensor at x=8, y=7: closest beacon is at x=2, y=10
+        Sensor at x=2, y=0: closest beacon is at x=2, y=10
+        Sensor at x=0, y=11: closest beacon is at x=2, y=10
+        Sensor at x=20, y=14: closest beacon is at x=25, y=17
+        Sensor at x=17, y=20: closest beacon is at x=21, y=22
+        Sensor at x=16, y=7: closest beacon is at x=15, y=3
+        Sensor at x=14, y=3: closest beacon is at x=15, y=3
+        Sensor at x=20, y=1: closest beacon is at x=15, y=3
+    "};
+
     #[test]
     fn tunnel_no_beacon_number_on_line() {
         // GIVEN
@@ -172,7 +291,7 @@ mod tests {
             Sensor at x=14, y=3: closest beacon is at x=15, y=3
             Sensor at x=20, y=1: closest beacon is at x=15, y=3
         "};
-        let tunnel = Tunnel::parse(input, 20, 20);
+        let tunnel = Tunnel::parse(input, Config::example()).unwrap();
 
         // WHEN
         let result = tunnel.number_of_not_beacons_on_line(10);
@@ -181,6 +300,46 @@ mod tests {
         assert_eq!(26, result);
     }
 
+    #[test]
+    fn tunnel_no_beacon_number_on_line_adjacent_ranges() {
+        // GIVEN
+        let input = indoc! {"
+            Sensor at x=0, y=0: closest beacon is at x=2, y=0
+            Sensor at x=5, y=0: closest beacon is at x=5, y=2
+            Sensor at x=100, y=50: closest beacon is at x=100, y=51
+        "};
+        let tunnel = Tunnel::parse(input, Config::example()).unwrap();
+
+        // WHEN
+        let result = tunnel.number_of_not_beacons_on_line(0);
+
+        // THEN
+        assert_eq!(9, result);
+    }
+
+    #[test]
+    fn tunnel_parse_invalid_line() {
+        // GIVEN
+        let input = indoc! {"
+            Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+            Sensor at x=9, y=16: closest beacon at x=10, y=16
+        "};
+
+        // WHEN
+        let result = Tunnel::parse(input, Config::example());
+
+        // THEN
+        assert_eq!(
+            Err(Error::Parse {
+                line: 2,
+                column: 1,
+                text: "Sensor at x=9, y=16: closest beacon at x=10, y=16".to_string(),
+                expected: "`Sensor at x=N, y=N: closest beacon is at x=N, y=N`".to_string(),
+            }),
+            result.map(|tunnel| tunnel.pairs.len())
+        );
+    }
+
     #[test]
     fn tunnel_tuning_frequency() {
         // GIVEN
@@ -200,12 +359,50 @@ mod tests {
             Sensor at x=14, y=3: closest beacon is at x=15, y=3
             Sensor at x=20, y=1: closest beacon is at x=15, y=3
         "};
-        let tunnel = Tunnel::parse(input, 20, 20);
+        let tunnel = Tunnel::parse(input, Config::example()).unwrap();
+
+        // WHEN
+        let result = tunnel.tuning_frequency();
+
+        // THEN
+        assert_eq!(Ok(56000011), result);
+    }
+
+    #[test]
+    fn tunnel_custom_config() {
+        // GIVEN
+        let config = Config {
+            multiplier: 10,
+            ..Config::example()
+        };
+        let tunnel = Tunnel::parse(EXAMPLE, config).unwrap();
+
+        // WHEN
+        let result = (tunnel.part1(), tunnel.part2());
+
+        // THEN
+        assert_eq!((Ok(26), Ok(151)), result);
+    }
+
+    #[test]
+    fn tunnel_tuning_frequency_overflow() {
+        // GIVEN
+        let config = Config {
+            multiplier: i64::MAX,
+            ..Config::example()
+        };
+        let tunnel = Tunnel::parse(EXAMPLE, config).unwrap();
 
         // WHEN
         let result = tunnel.tuning_frequency();
 
         // THEN
-        assert_eq!(56000011, result);
+        assert_eq!(
+            Err(Error::InvalidArgument(format!(
+                "the tuning frequency overflows with a multiplier of {}",
+                i64::MAX
+            ))),
+            result
+        );
     }
 }
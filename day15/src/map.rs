@@ -0,0 +1,307 @@
+//! Drawing what the sensors cover, as text for small areas or as a downsampled image.
+
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use common::{Error, Result};
+
+use crate::{BeaconSearch, ClampedRange, Tunnel};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MapFormat {
+    /// `S`, `B`, `#` and `.` characters, one per position, and `X` for the distress
+    /// beacon
+    Ascii,
+    /// Plain PGM image
+    Pgm,
+    /// PNG image
+    Png,
+}
+
+impl FromStr for MapFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ascii" => Ok(MapFormat::Ascii),
+            "pgm" => Ok(MapFormat::Pgm),
+            "png" => Ok(MapFormat::Png),
+            _ => Err(Error::InvalidArgument(format!(
+                "expected one of `ascii`, `pgm` or `png`, found `{s}`"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Tile {
+    Uncovered,
+    Covered,
+    Beacon,
+    Sensor,
+    Distress,
+}
+
+impl Tile {
+    fn char(self) -> char {
+        match self {
+            Tile::Uncovered => '.',
+            Tile::Distress => 'X',
+            Tile::Covered => '#',
+            Tile::Beacon => 'B',
+            Tile::Sensor => 'S',
+        }
+    }
+
+    fn grey(self) -> u8 {
+        match self {
+            Tile::Uncovered => 0,
+            Tile::Covered => 96,
+            Tile::Beacon => 160,
+            Tile::Sensor => 208,
+            Tile::Distress => 255,
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Tile::Uncovered => [0, 0, 0],
+            Tile::Covered => [96, 96, 96],
+            Tile::Beacon => [64, 192, 64],
+            Tile::Sensor => [64, 128, 255],
+            Tile::Distress => [255, 32, 32],
+        }
+    }
+}
+
+/// Area of the tunnel drawn with one tile per `scale` x `scale` block of positions.
+struct Map {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+}
+
+impl Map {
+    fn rows(&self) -> impl Iterator<Item = &[Tile]> {
+        self.tiles.chunks(self.width)
+    }
+
+    fn ascii(&self) -> String {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| tile.char())
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn pgm(&self) -> String {
+        let mut image = format!("P2\n{} {}\n255\n", self.width, self.height);
+        for row in self.rows() {
+            let values: Vec<_> = row.iter().map(|tile| tile.grey().to_string()).collect();
+            image += &values.join(" ");
+            image.push('\n');
+        }
+        image
+    }
+
+    fn png(&self) -> Result<Vec<u8>> {
+        let failed = |e: png::EncodingError| Error::Output(format!("cannot encode PNG: {e}"));
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let data: Vec<u8> = self.tiles.iter().flat_map(|tile| tile.rgb()).collect();
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&data))
+            .map_err(failed)?;
+        Ok(bytes)
+    }
+}
+
+/// Largest side of a text map, which is always drawn at full resolution.
+const MAX_ASCII_SIDE: u64 = 1000;
+
+impl Tunnel {
+    /// Draws the window with at most `size` pixels on each side for images; text is
+    /// always at full resolution, so only small windows can be drawn as text. Text
+    /// marks the distress beacon with an `X`, images with a cross.
+    pub fn render_map(
+        &self,
+        format: MapFormat,
+        xs: RangeInclusive<i64>,
+        ys: RangeInclusive<i64>,
+        size: usize,
+    ) -> Result<Vec<u8>> {
+        if xs.is_empty() || ys.is_empty() {
+            return Err(Error::InvalidArgument("the window is empty".to_string()));
+        }
+        let (width, height) = (
+            xs.end().abs_diff(*xs.start()) + 1,
+            ys.end().abs_diff(*ys.start()) + 1,
+        );
+        match format {
+            MapFormat::Ascii if width.max(height) > MAX_ASCII_SIDE => {
+                Err(Error::InvalidArgument(format!(
+                    "the window is {width}x{height} positions, too large to draw as text \
+                     (at most {MAX_ASCII_SIDE} on each side)"
+                )))
+            }
+            MapFormat::Ascii => Ok(self.map(xs, ys, 1, 0).ascii().into_bytes()),
+            MapFormat::Pgm | MapFormat::Png => {
+                let extent = width.max(height);
+                let scale = extent.div_ceil(size.max(1) as u64) as i64;
+                let map = self.map(xs, ys, scale, 2);
+                match format {
+                    MapFormat::Pgm => Ok(map.pgm().into_bytes()),
+                    _ => map.png(),
+                }
+            }
+        }
+    }
+
+    fn map(&self, xs: RangeInclusive<i64>, ys: RangeInclusive<i64>, scale: i64, cross: i64) -> Map {
+        let (x_min, y_min) = (*xs.start(), *ys.start());
+        let width = ((xs.end() - x_min) / scale + 1) as usize;
+        let height = ((ys.end() - y_min) / scale + 1) as usize;
+
+        // Coverage is sampled at the center of each block
+        let mut tiles = Vec::with_capacity(width * height);
+        for j in 0..height as i64 {
+            let y = y_min + j * scale + scale / 2;
+            let ranges = self.covered_on_line(y, i64::MIN, i64::MAX);
+            tiles.extend((0..width as i64).map(|i| {
+                let x = x_min + i * scale + scale / 2;
+                if ranges.iter().any(|range: &ClampedRange| range.contains(x)) {
+                    Tile::Covered
+                } else {
+                    Tile::Uncovered
+                }
+            }));
+        }
+
+        let mut map = Map {
+            width,
+            height,
+            tiles,
+        };
+        let mut draw = |x: i64, y: i64, tile: Tile| {
+            if xs.contains(&x) && ys.contains(&y) {
+                let (i, j) = ((x - x_min) / scale, (y - y_min) / scale);
+                map.tiles[j as usize * width + i as usize] = tile;
+            }
+        };
+        for (_, beacon) in &self.pairs {
+            draw(beacon.x, beacon.y, Tile::Beacon);
+        }
+        for (sensor, _) in &self.pairs {
+            draw(sensor.x, sensor.y, Tile::Sensor);
+        }
+        // The arms of the cross are `cross` tiles long
+        if let Some(distress) = self.find_beacon_with(BeaconSearch::Edges) {
+            for offset in -cross..=cross {
+                draw(distress.x + offset * scale, distress.y, Tile::Distress);
+                draw(distress.x, distress.y + offset * scale, Tile::Distress);
+            }
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::EXAMPLE;
+    use crate::Config;
+    use indoc::indoc;
+
+    #[test]
+    fn map_ascii() {
+        // GIVEN
+        let tunnel = Tunnel::parse(EXAMPLE, Config::example()).unwrap();
+
+        // WHEN
+        let map = tunnel.render_map(MapFormat::Ascii, 8..=16, 7..=16, 1);
+
+        // THEN the distress beacon is at (14, 11)
+        let expected = indoc! {"
+            S#######S
+            #########
+            #########
+            #########
+            ######X##
+            #########
+            #########
+            ####S####
+            #########
+            #SB######
+        "};
+        assert_eq!(Ok(expected.as_bytes().to_vec()), map);
+    }
+
+    #[test]
+    fn map_downsampled_pgm() {
+        // GIVEN
+        let tunnel = Tunnel::parse(EXAMPLE, Config::example()).unwrap();
+
+        // WHEN
+        let map = tunnel
+            .render_map(MapFormat::Pgm, 0..=20, 0..=20, 7)
+            .unwrap();
+
+        // THEN
+        let expected = indoc! {"
+            P2
+            7 7
+            255
+            208 96 96 96 208 96 208
+            96 96 96 96 255 160 96
+            96 96 208 96 255 208 96
+            208 96 255 255 255 255 255
+            96 96 96 96 255 96 208
+            96 96 96 208 255 96 96
+            208 96 96 208 96 208 96
+        "};
+        assert_eq!(expected, String::from_utf8(map).unwrap());
+    }
+
+    #[test]
+    fn map_png_signature() {
+        // GIVEN
+        let input = indoc! {"
+            Sensor at x=8, y=7: closest beacon is at x=2, y=10
+            Sensor at x=5, y=1: closest beacon is at x=5, y=2
+        "};
+        let tunnel = Tunnel::parse(input, Config::example()).unwrap();
+
+        // WHEN
+        let map = tunnel
+            .render_map(MapFormat::Png, 0..=20, 0..=20, 100)
+            .unwrap();
+
+        // THEN
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &map[..8]);
+    }
+
+    #[test]
+    fn map_ascii_too_large() {
+        // GIVEN
+        let input = indoc! {"
+            Sensor at x=8, y=7: closest beacon is at x=2, y=10
+        "};
+        let tunnel = Tunnel::parse(input, Config::default()).unwrap();
+
+        // WHEN
+        let map = tunnel.render_map(MapFormat::Ascii, 0..=4000000, 0..=4000000, 1000);
+
+        // THEN
+        assert_eq!(
+            "invalid argument: the window is 4000001x4000001 positions, too large to draw \
+             as text (at most 1000 on each side)",
+            map.err().unwrap().to_string()
+        );
+    }
+}
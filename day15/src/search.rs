@@ -0,0 +1,153 @@
+//! Ways of finding the distress beacon, the only position in bounds no sensor covers.
+
+use std::str::FromStr;
+
+use common::{Error, Result};
+
+use crate::{Point, Tunnel};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BeaconSearch {
+    /// Merge the covered ranges of every row, in parallel
+    RowScan,
+    /// Only test where the edges just outside the sensor diamonds cross
+    Edges,
+}
+
+impl FromStr for BeaconSearch {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "row-scan" => Ok(BeaconSearch::RowScan),
+            "edges" => Ok(BeaconSearch::Edges),
+            _ => Err(Error::InvalidArgument(format!(
+                "expected `row-scan` or `edges`, found `{s}`"
+            ))),
+        }
+    }
+}
+
+impl Tunnel {
+    /// A lone uncovered position is surrounded by covered ones, so it lies on the
+    /// edge just outside some diamonds: where two such edges cross, or where one
+    /// meets the bounds.
+    pub(crate) fn find_beacon_by_edges(&self) -> Option<Point> {
+        // Edges going down-left keep x + y constant, those going down-right x - y
+        let mut sums = Vec::new();
+        let mut differences = Vec::new();
+        for (sensor, beacon) in &self.pairs {
+            let outside = sensor.distance(beacon) as i64 + 1;
+            let (sum, difference) = (sensor.x + sensor.y, sensor.x - sensor.y);
+            sums.extend([sum - outside, sum + outside]);
+            differences.extend([difference - outside, difference + outside]);
+        }
+
+        let (x_bound, y_bound) = (self.x_bound, self.y_bound);
+        let crossings = sums.iter().flat_map(|&a| {
+            differences
+                .iter()
+                .filter(move |&&b| (a + b) % 2 == 0)
+                .map(move |&b| ((a + b) / 2, (a - b) / 2))
+        });
+        let on_bounds = sums
+            .iter()
+            .flat_map(|&a| {
+                [
+                    (0, a),
+                    (x_bound, a - x_bound),
+                    (a, 0),
+                    (a - y_bound, y_bound),
+                ]
+            })
+            .chain(differences.iter().flat_map(|&b| {
+                [
+                    (0, -b),
+                    (x_bound, x_bound - b),
+                    (b, 0),
+                    (b + y_bound, y_bound),
+                ]
+            }));
+        let corners = [(0, 0), (x_bound, 0), (0, y_bound), (x_bound, y_bound)];
+
+        crossings
+            .chain(on_bounds)
+            .chain(corners)
+            .filter(|&(x, y)| (0..=x_bound).contains(&x) && (0..=y_bound).contains(&y))
+            .map(|(x, y)| Point { x, y })
+            .filter(|point| !self.is_covered(point))
+            .min_by_key(|point| (point.y, point.x))
+    }
+
+    fn is_covered(&self, point: &Point) -> bool {
+        self.pairs
+            .iter()
+            .any(|(sensor, beacon)| sensor.distance(point) <= sensor.distance(beacon))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::EXAMPLE;
+    use crate::Config;
+    use indoc::indoc;
+
+    #[test]
+    fn search_strategies_agree() {
+        // GIVEN
+        let tunnel = Tunnel::parse(EXAMPLE, Config::example()).unwrap();
+
+        // WHEN
+        let row_scan = tunnel.find_beacon_with(BeaconSearch::RowScan);
+        let edges = tunnel.find_beacon_with(BeaconSearch::Edges);
+
+        // THEN
+        assert_eq!(Some(Point { x: 14, y: 11 }), row_scan);
+        assert_eq!(row_scan, edges);
+    }
+
+    #[test]
+    fn search_beacon_in_a_corner() {
+        // GIVEN
+        let input = "Sensor at x=20, y=20: closest beacon is at x=20, y=59\n";
+        let tunnel = Tunnel::parse(input, Config::example()).unwrap();
+
+        // WHEN
+        let row_scan = tunnel.find_beacon_with(BeaconSearch::RowScan);
+        let edges = tunnel.find_beacon_with(BeaconSearch::Edges);
+
+        // THEN
+        assert_eq!(Some(Point { x: 0, y: 0 }), row_scan);
+        assert_eq!(row_scan, edges);
+    }
+
+    #[test]
+    fn search_very_large_bounds() {
+        // GIVEN
+        let input = indoc! {"
+            Sensor at x=0, y=0: closest beacon is at x=3999999999999, y=0
+            Sensor at x=4000000000000, y=0: closest beacon is at x=1, y=0
+            Sensor at x=0, y=4000000000000: closest beacon is at x=3999999999999, y=4000000000000
+            Sensor at x=4000000000000, y=4000000000000: closest beacon is at x=1, y=4000000000000
+        "};
+        let config = Config {
+            x_bound: 4000000000000,
+            y_bound: 4000000000000,
+            ..Config::default()
+        };
+        let tunnel = Tunnel::parse(input, config).unwrap();
+
+        // WHEN
+        let result = tunnel.find_beacon_with(BeaconSearch::Edges);
+
+        // THEN
+        assert_eq!(
+            Some(Point {
+                x: 2000000000000,
+                y: 2000000000000
+            }),
+            result
+        );
+    }
+}
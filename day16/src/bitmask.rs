@@ -0,0 +1,231 @@
+//! Dynamic programming over the sets of opened valves, each set being a bitmask of
+//! the valves worth opening.
+
+use itertools::Itertools;
+
+use common::{Error, Result};
+
+use crate::Cave;
+
+/// Most valves worth opening the search handles: it keeps a few tables with an
+/// entry for each of their subsets.
+pub(crate) const MAX_VALVES: usize = 20;
+
+impl Cave {
+    /// Valves worth opening, in the order of the bits of the masks.
+    pub(crate) fn worth_opening(&self) -> Vec<usize> {
+        self.potential_valves.iter().copied().sorted().collect()
+    }
+
+    /// Best pressure released by opening exactly each set of valves, in any order,
+    /// within `minutes`. A single pass over every reachable sequence of valves.
+    fn best_pressure_per_mask(&self, start: usize, minutes: usize) -> Vec<usize> {
+        let valves = self.worth_opening();
+        let mut best = vec![0; 1 << valves.len()];
+        self.visit(&valves, minutes, start, 0, 0, &mut best);
+        best
+    }
+
+    fn visit(
+        &self,
+        valves: &[usize],
+        remaining_minutes: usize,
+        current_valve: usize,
+        mask: usize,
+        pressure: usize,
+        best: &mut [usize],
+    ) {
+        best[mask] = best[mask].max(pressure);
+        for (bit, &valve) in valves.iter().enumerate() {
+            let Some(distance) = self.distances.distance(current_valve, valve) else {
+                continue;
+            };
+            let cost = distance + 1;
+            if mask & (1 << bit) != 0 || remaining_minutes <= cost {
+                continue;
+            }
+            let remaining_minutes = remaining_minutes - cost;
+            self.visit(
+                valves,
+                remaining_minutes,
+                valve,
+                mask | (1 << bit),
+                pressure + remaining_minutes * self.flow_rates[valve],
+                best,
+            );
+        }
+    }
+
+    /// Best pressure released by the actors, and the set of valves each one opens.
+    /// The actors open disjoint sets: the best total within a set gives its best
+    /// subset to the first actor, and the best total within the rest to the others.
+    pub(crate) fn best_masks(
+        &self,
+        start: usize,
+        minutes: usize,
+        actors: usize,
+    ) -> Result<(usize, Vec<usize>)> {
+        let count = self.potential_valves.len();
+        if count > MAX_VALVES {
+            return Err(Error::InvalidArgument(format!(
+                "the bitmask search handles at most {MAX_VALVES} valves worth opening, not {count}"
+            )));
+        }
+        if actors == 0 {
+            return Ok((0, Vec::new()));
+        }
+        let best = self.best_pressure_per_mask(start, minutes);
+        let all = best.len() - 1;
+
+        // For a single actor, the best subset of each set, and its pressure
+        let mut within: Vec<(usize, usize)> = best
+            .iter()
+            .enumerate()
+            .map(|(mask, &p)| (p, mask))
+            .collect();
+        for bit in 0..all.count_ones() {
+            for mask in 0..=all {
+                if mask & (1 << bit) != 0 {
+                    within[mask] = within[mask].max(within[mask ^ (1 << bit)]);
+                }
+            }
+        }
+
+        // Each further actor adds a level; the last one is only needed for all valves
+        let mut levels = vec![within];
+        for actor in 1..actors {
+            let previous = levels.last().unwrap();
+            let masks: Vec<usize> = if actor + 1 == actors {
+                vec![all]
+            } else {
+                (0..=all).collect()
+            };
+            let mut level = vec![(0, 0); all + 1];
+            for mask in masks {
+                level[mask] = submasks(mask)
+                    .map(|sub| (best[sub] + previous[mask ^ sub].0, sub))
+                    .max()
+                    .unwrap();
+            }
+            levels.push(level);
+        }
+
+        let pressure = levels.last().unwrap()[all].0;
+        let mut remaining = all;
+        let mut masks = Vec::new();
+        for level in levels.iter().rev() {
+            let mask = level[remaining].1;
+            masks.push(mask);
+            remaining ^= mask;
+        }
+        Ok((pressure, masks))
+    }
+}
+
+/// Every subset of `mask`, itself and the empty set included.
+fn submasks(mask: usize) -> impl Iterator<Item = usize> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let sub = next?;
+        next = (sub != 0).then(|| (sub - 1) & mask);
+        Some(sub)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cave, Solver};
+    use indoc::indoc;
+
+    #[test]
+    fn bitmask_matches_branch_and_bound() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnels lead to valves BA, CA
+            Valve BA has flow rate=7; tunnels lead to valves AA, BB
+            Valve BB has flow rate=0; tunnels lead to valves BA, BC
+            Valve BC has flow rate=19; tunnels lead to valves BB, CC
+            Valve CA has flow rate=4; tunnels lead to valves AA, CB
+            Valve CB has flow rate=11; tunnels lead to valves CA, CC
+            Valve CC has flow rate=0; tunnels lead to valves CB, BC, DD
+            Valve DD has flow rate=25; tunnel leads to valve CC
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let alone = [Solver::BranchAndBound, Solver::Bitmask].map(|s| cave.max_pressure_using(s));
+        let together = [Solver::BranchAndBound, Solver::Bitmask]
+            .map(|s| cave.max_pressure_with_elephant_using(s));
+
+        // THEN
+        assert_eq!(alone[0], alone[1]);
+        assert_eq!(together[0], together[1]);
+    }
+
+    #[test]
+    fn bitmask_no_valve_worth_opening() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnel leads to valve BB
+            Valve BB has flow rate=0; tunnel leads to valve AA
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let result = (
+            cave.max_pressure_using(Solver::Bitmask),
+            cave.max_pressure_with_elephant_using(Solver::Bitmask),
+        );
+
+        // THEN
+        assert_eq!((Ok(0), Ok(0)), result);
+    }
+
+    #[test]
+    fn bitmask_unreachable_valves() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnel leads to valve BB
+            Valve BB has flow rate=5; tunnel leads to valve AA
+            Valve CC has flow rate=10; tunnel leads to valve DD
+            Valve DD has flow rate=20; tunnel leads to valve CC
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let result = (
+            cave.max_pressure_using(Solver::Bitmask),
+            cave.max_pressure_with_elephant_using(Solver::Bitmask),
+        );
+
+        // THEN
+        assert_eq!((Ok(140), Ok(120)), result);
+    }
+
+    #[test]
+    fn bitmask_too_many_valves() {
+        // GIVEN AA - BB, and a ring of 20 more valves out of reach
+        let mut input = indoc! {"
+            Valve AA has flow rate=0; tunnel leads to valve BB
+            Valve BB has flow rate=5; tunnel leads to valve AA
+            "}
+        .to_string();
+        let ring: Vec<char> = ('C'..='V').collect();
+        for (i, name) in ring.iter().enumerate() {
+            let next = ring[(i + 1) % ring.len()];
+            input += &format!("Valve R{name} has flow rate=1; tunnel leads to valve R{next}\n");
+        }
+        let cave = Cave::parse(&input).unwrap();
+
+        // WHEN
+        let bitmask = cave.max_pressure_using(Solver::Bitmask);
+        let default = cave.max_pressure();
+
+        // THEN
+        assert_eq!(
+            "invalid argument: the bitmask search handles at most 20 valves worth opening, not 21",
+            bitmask.unwrap_err().to_string()
+        );
+        assert_eq!(Ok(140), default);
+    }
+}
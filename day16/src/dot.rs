@@ -0,0 +1,157 @@
+//! Graphviz export of the valves, to draw the cave with `dot -Tsvg`.
+
+use std::fmt::Write;
+
+use common::Result;
+
+use crate::Cave;
+
+impl Cave {
+    /// Every valve and tunnel. Tunnels going both ways are drawn once, without arrows.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph valves {\n");
+        for valve in 0..self.names.len() {
+            self.write_node(&mut dot, valve);
+        }
+        for (valve, tunnels) in self.tunnels.iter().enumerate() {
+            for &next in tunnels {
+                let both_ways = self.tunnels[next].contains(&valve);
+                if both_ways && next < valve {
+                    continue;
+                }
+                let attributes = if both_ways { "" } else { " [dir=forward]" };
+                writeln!(
+                    dot,
+                    "  \"{}\" -- \"{}\"{attributes};",
+                    self.names[valve], self.names[next]
+                )
+                .unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Only the start and the valves worth opening, each pair linked by the length
+    /// of the shortest path between them, in each direction when they differ.
+    pub fn to_compressed_dot(&self, start: &str) -> Result<String> {
+        let start = self.valve(start)?;
+        let mut valves = self.worth_opening();
+        if !valves.contains(&start) {
+            valves.insert(0, start);
+        }
+
+        let mut dot = String::from("graph valves {\n");
+        for &valve in &valves {
+            self.write_node(&mut dot, valve);
+        }
+        for (i, &a) in valves.iter().enumerate() {
+            for &b in &valves[i + 1..] {
+                let there = self.distances.distance(a, b);
+                let back = self.distances.distance(b, a);
+                // One-way tunnels can make the way back longer, or impossible
+                let edges = if there == back {
+                    vec![(a, b, there, "")]
+                } else {
+                    vec![
+                        (a, b, there, "dir=forward, "),
+                        (b, a, back, "dir=forward, "),
+                    ]
+                };
+                for (from, to, distance, direction) in edges {
+                    let Some(distance) = distance else {
+                        continue;
+                    };
+                    writeln!(
+                        dot,
+                        "  \"{}\" -- \"{}\" [{direction}label={distance}, weight={distance}];",
+                        self.names[from], self.names[to]
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+
+    fn write_node(&self, dot: &mut String, valve: usize) {
+        let flow_rate = self.flow_rates[valve];
+        let style = if flow_rate == 0 {
+            "style=dashed, color=gray50, fontcolor=gray50"
+        } else {
+            "style=bold"
+        };
+        writeln!(
+            dot,
+            "  \"{0}\" [label=\"{0}\\n{flow_rate}\", {style}];",
+            self.names[valve]
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Cave;
+    use indoc::indoc;
+
+    #[test]
+    fn dot_tunnels() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnels lead to valves BB, CC
+            Valve BB has flow rate=13; tunnels lead to valves AA, DD
+            Valve CC has flow rate=0; tunnels lead to valves AA, DD
+            Valve DD has flow rate=2; tunnel leads to valve CC
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let dot = cave.to_dot();
+
+        // THEN
+        let expected = indoc! {r#"
+            graph valves {
+              "AA" [label="AA\n0", style=dashed, color=gray50, fontcolor=gray50];
+              "BB" [label="BB\n13", style=bold];
+              "CC" [label="CC\n0", style=dashed, color=gray50, fontcolor=gray50];
+              "DD" [label="DD\n2", style=bold];
+              "AA" -- "BB";
+              "AA" -- "CC";
+              "BB" -- "DD" [dir=forward];
+              "CC" -- "DD";
+            }
+        "#};
+        assert_eq!(expected, dot);
+    }
+
+    #[test]
+    fn dot_compressed() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnels lead to valves BB, CC
+            Valve BB has flow rate=13; tunnels lead to valves AA, DD
+            Valve CC has flow rate=0; tunnels lead to valves AA, DD
+            Valve DD has flow rate=2; tunnel leads to valve CC
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let dot = cave.to_compressed_dot("AA").unwrap();
+
+        // THEN
+        let expected = indoc! {r#"
+            graph valves {
+              "AA" [label="AA\n0", style=dashed, color=gray50, fontcolor=gray50];
+              "BB" [label="BB\n13", style=bold];
+              "DD" [label="DD\n2", style=bold];
+              "AA" -- "BB" [label=1, weight=1];
+              "AA" -- "DD" [label=2, weight=2];
+              "BB" -- "DD" [dir=forward, label=1, weight=1];
+              "DD" -- "BB" [dir=forward, label=3, weight=3];
+            }
+        "#};
+        assert_eq!(expected, dot);
+    }
+}
@@ -1,14 +1,19 @@
 use std::{
     cell::RefCell,
-    cmp::min,
     collections::{BTreeMap, HashSet},
-    ops::{Index, IndexMut},
+    str::FromStr,
 };
 
+use common::{Error, Result, Solution};
+use graph::{AdjacencyList, AllPairs};
 use itertools::Itertools;
 
 use regex::Regex;
 
+mod bitmask;
+mod dot;
+pub mod plan;
+
 #[derive(Debug)]
 struct NamedValve {
     flow_rate: usize,
@@ -42,84 +47,60 @@ impl Valve {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Matrix {
-    width: usize,
-    cells: Vec<usize>,
-}
-impl Matrix {
-    fn from_edge_vector(valves: &[Valve]) -> Self {
-        let width = valves.len();
-        let mut adjacency_matrix = Matrix {
-            width,
-            cells: vec![usize::MAX; width * width],
-        };
-        for (valve_index, valve) in valves.iter().enumerate() {
-            for neighbor_index in valve.tunnels.iter() {
-                adjacency_matrix[(valve_index, *neighbor_index)] = 1;
-            }
-        }
-
-        adjacency_matrix.floyd_warshall();
-        adjacency_matrix
-    }
-
-    fn floyd_warshall(&mut self) {
-        for i in 0..self.width {
-            self[(i, i)] = 0;
-        }
-
-        for k in 0..self.width {
-            for i in 0..self.width {
-                for j in 0..self.width {
-                    self[(i, j)] = min(self[(i, j)], self[(i, k)].saturating_add(self[(k, j)]));
-                }
-            }
-        }
-    }
-}
-
-impl Index<(usize, usize)> for Matrix {
-    type Output = usize;
-
-    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
-        &self.cells[i + j * self.width]
-    }
-}
-
-impl IndexMut<(usize, usize)> for Matrix {
-    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
-        &mut self.cells[i + j * self.width]
-    }
-}
-
 pub struct Cave {
-    distances: Matrix,
+    distances: AllPairs,
     potential_valves: HashSet<usize>,
     flow_rates: Vec<usize>,
+    names: Vec<String>,
+    tunnels: Vec<Vec<usize>>,
 }
 
 impl Cave {
-    pub fn parse(input: &str) -> Cave {
+    pub fn parse(input: &str) -> Result<Cave> {
         let re = Regex::new(
             r"Valve ([A-Z]{2}) has flow rate=(\d+); tunnels? leads? to valves? (([A-Z]{2}, )*[A-Z]{2})",
         )
         .unwrap();
-        let named_valves: BTreeMap<String, NamedValve> = re
-            .captures_iter(input)
-            .map(|cap| {
-                (
-                    cap[1].to_string(),
-                    NamedValve {
-                        flow_rate: cap[2].parse().unwrap(),
-                        tunnels: cap[3].split(", ").map(|v| v.to_string()).collect(),
-                    },
+        let mut named_valves: BTreeMap<String, NamedValve> = BTreeMap::new();
+        let mut all_tunnels = Vec::new();
+        for line in input.lines() {
+            let cap = re.captures(line).ok_or_else(|| {
+                Error::parse(
+                    input,
+                    line,
+                    "`Valve XX has flow rate=N; tunnels lead to valves XX, YY`",
                 )
-            })
-            .collect();
+            })?;
+            let flow_rate = cap.get(2).map(|m| m.as_str()).unwrap_or_default();
+            let tunnels = cap.get(3).map(|m| m.as_str()).unwrap_or_default();
+            all_tunnels.extend(tunnels.split(", "));
+            named_valves.insert(
+                cap[1].to_string(),
+                NamedValve {
+                    flow_rate: flow_rate
+                        .parse()
+                        .map_err(|_| Error::parse(input, flow_rate, "a flow rate"))?,
+                    tunnels: tunnels.split(", ").map(|v| v.to_string()).collect(),
+                },
+            );
+        }
+        if let Some(unknown) = all_tunnels.iter().find(|v| !named_valves.contains_key(**v)) {
+            return Err(Error::parse(
+                input,
+                unknown,
+                "the name of a described valve",
+            ));
+        }
 
+        let names = named_valves.keys().cloned().collect();
         let valves = Valve::from_named_valves(named_valves);
-        let distances = Matrix::from_edge_vector(&valves);
+        let mut tunnel_graph = AdjacencyList::new(valves.len());
+        for (valve_index, valve) in valves.iter().enumerate() {
+            for neighbor_index in valve.tunnels.iter() {
+                tunnel_graph.add_edge(valve_index, *neighbor_index, 1);
+            }
+        }
+        let distances = tunnel_graph.to_matrix().floyd_warshall();
 
         let flow_rates = valves.iter().map(|v| v.flow_rate).collect();
 
@@ -130,31 +111,90 @@ impl Cave {
             .map(|(i, _v)| i)
             .collect();
 
-        Cave {
+        let tunnels = valves.into_iter().map(|v| v.tunnels).collect();
+
+        Ok(Cave {
             distances,
             potential_valves,
             flow_rates,
+            names,
+            tunnels,
+        })
+    }
+
+    pub fn max_pressure(&self) -> Result<usize> {
+        self.max_pressure_using(self.default_solver())
+    }
+
+    pub fn max_pressure_with_elephant(&self) -> Result<usize> {
+        self.max_pressure_with_elephant_using(self.default_solver())
+    }
+
+    /// The bitmask search, unless there are too many valves worth opening for it.
+    fn default_solver(&self) -> Solver {
+        if self.potential_valves.len() <= bitmask::MAX_VALVES {
+            Solver::Bitmask
+        } else {
+            Solver::BranchAndBound
         }
     }
 
-    pub fn max_pressure(&self) -> usize {
-        self.pressure(30, 0, 0, self.potential_valves.clone(), &RefCell::new(0))
+    pub fn max_pressure_using(&self, solver: Solver) -> Result<usize> {
+        self.best_pressure(&Scenario::alone(), solver)
+    }
+
+    pub fn max_pressure_with_elephant_using(&self, solver: Solver) -> Result<usize> {
+        self.best_pressure(&Scenario::with_elephant(), solver)
+    }
+
+    pub fn best_pressure(&self, scenario: &Scenario, solver: Solver) -> Result<usize> {
+        let start = self.valve(&scenario.start)?;
+        match solver {
+            Solver::BranchAndBound => self.best_pressure_by_branch_and_bound(start, scenario),
+            Solver::Bitmask => Ok(self.best_masks(start, scenario.minutes, scenario.actors)?.0),
+        }
     }
 
-    pub fn max_pressure_with_elephant(&self) -> usize {
-        self.potential_valves
+    fn valve(&self, name: &str) -> Result<usize> {
+        self.names
             .iter()
-            .copied()
-            .powerset()
-            .map(|set| {
-                let set = HashSet::from_iter(set);
-                let complement = &self.potential_valves - &set;
+            .position(|valve| valve == name)
+            .ok_or_else(|| Error::InvalidArgument(format!("there is no valve named `{name}`")))
+    }
 
-                self.pressure(26, 0, 0, set, &RefCell::new(0))
-                    + self.pressure(26, 0, 0, complement, &RefCell::new(0))
-            })
-            .max()
-            .unwrap()
+    fn best_pressure_by_branch_and_bound(
+        &self,
+        start: usize,
+        scenario: &Scenario,
+    ) -> Result<usize> {
+        let minutes = scenario.minutes;
+        match scenario.actors {
+            0 => Ok(0),
+            1 => Ok(self.pressure(
+                minutes,
+                start,
+                0,
+                self.potential_valves.clone(),
+                &RefCell::new(0),
+            )),
+            2 => Ok(self
+                .potential_valves
+                .iter()
+                .copied()
+                .powerset()
+                .map(|set| {
+                    let set = HashSet::from_iter(set);
+                    let complement = &self.potential_valves - &set;
+
+                    self.pressure(minutes, start, 0, set, &RefCell::new(0))
+                        + self.pressure(minutes, start, 0, complement, &RefCell::new(0))
+                })
+                .max()
+                .unwrap()),
+            _ => Err(Error::InvalidArgument(
+                "the branch and bound search only handles one or two actors".to_string(),
+            )),
+        }
     }
 
     fn pressure(
@@ -182,10 +222,12 @@ impl Cave {
         remaining_valves
             .iter()
             .copied()
-            .filter(|valve| remaining_minutes > (self.distances[(current_valve, *valve)] + 1))
-            .map(|valve| {
-                let new_remaining_minutes =
-                    remaining_minutes - (self.distances[(current_valve, valve)] + 1);
+            .filter_map(|valve| {
+                let cost = self.distances.distance(current_valve, valve)? + 1;
+                (remaining_minutes > cost).then_some((valve, cost))
+            })
+            .map(|(valve, cost)| {
+                let new_remaining_minutes = remaining_minutes - cost;
                 let new_pressure =
                     current_pressure + new_remaining_minutes * self.flow_rates[valve];
                 let new_remaining_valves = &remaining_valves - &HashSet::from([valve]);
@@ -202,9 +244,78 @@ impl Cave {
     }
 }
 
+/// Where the actors start, for how long, and how many of them open valves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scenario {
+    pub start: String,
+    pub minutes: usize,
+    pub actors: usize,
+}
+
+impl Scenario {
+    /// Part 1: 30 minutes alone.
+    pub fn alone() -> Self {
+        Scenario {
+            start: "AA".to_string(),
+            minutes: 30,
+            actors: 1,
+        }
+    }
+
+    /// Part 2: 26 minutes, after teaching an elephant to help.
+    pub fn with_elephant() -> Self {
+        Scenario {
+            minutes: 26,
+            actors: 2,
+            ..Scenario::alone()
+        }
+    }
+}
+
+/// How to search for the best order in which to open the valves.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Solver {
+    /// Try every order, pruning those that cannot beat the best one so far
+    BranchAndBound,
+    /// Keep the best pressure for each set of opened valves
+    Bitmask,
+}
+
+impl FromStr for Solver {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "branch-and-bound" => Ok(Solver::BranchAndBound),
+            "bitmask" => Ok(Solver::Bitmask),
+            _ => Err(Error::InvalidArgument(format!(
+                "expected `branch-and-bound` or `bitmask`, found `{s}`"
+            ))),
+        }
+    }
+}
+
+impl Solution for Cave {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Cave::parse(input)
+    }
+
+    fn part1(&self) -> Result<Self::Part1> {
+        self.max_pressure()
+    }
+
+    fn part2(&self) -> Result<Self::Part2> {
+        self.max_pressure_with_elephant()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use graph::Matrix;
     use indoc::indoc;
 
     #[test]
@@ -217,15 +328,30 @@ mod tests {
             "};
 
         // WHEN
-        let graph = Cave::parse(input);
+        let graph = Cave::parse(input).unwrap();
+
+        // THEN
+        assert_eq!(
+            &Matrix::from_cells(3, vec![0, 1, 2, 1, 0, 1, 2, 1, 0,]),
+            graph.distances.distances()
+        );
+    }
+
+    #[test]
+    fn graph_unknown_tunnel() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnel leads to valve BB
+            Valve BB has flow rate=13; tunnels lead to valves AA, CC
+            "};
+
+        // WHEN
+        let result = Cave::parse(input);
 
         // THEN
         assert_eq!(
-            Matrix {
-                width: 3,
-                cells: vec![0, 1, 2, 1, 0, 1, 2, 1, 0,]
-            },
-            graph.distances
+            "line 2, column 55: expected the name of a described valve, found `CC`",
+            result.err().unwrap().to_string()
         );
     }
 
@@ -244,13 +370,13 @@ mod tests {
             Valve II has flow rate=0; tunnels lead to valves AA, JJ
             Valve JJ has flow rate=21; tunnel leads to valve II
             "};
-        let graph = Cave::parse(input);
+        let graph = Cave::parse(input).unwrap();
 
         // WHEN
         let result = graph.max_pressure();
 
         // THEN
-        assert_eq!(1651, result);
+        assert_eq!(Ok(1651), result);
     }
 
     #[test]
@@ -268,12 +394,84 @@ mod tests {
             Valve II has flow rate=0; tunnels lead to valves AA, JJ
             Valve JJ has flow rate=21; tunnel leads to valve II
             "};
-        let graph = Cave::parse(input);
+        let graph = Cave::parse(input).unwrap();
 
         // WHEN
         let result = graph.max_pressure_with_elephant();
 
         // THEN
-        assert_eq!(1707, result);
+        assert_eq!(Ok(1707), result);
+    }
+
+    #[test]
+    fn graph_named_start_and_actors() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AB has flow rate=10; tunnel leads to valve ZZ
+            Valve AC has flow rate=5; tunnel leads to valve ZZ
+            Valve AD has flow rate=1; tunnel leads to valve ZZ
+            Valve ZZ has flow rate=0; tunnels lead to valves AB, AC, AD
+            "};
+        let graph = Cave::parse(input).unwrap();
+        let scenario = |actors| Scenario {
+            start: "ZZ".to_string(),
+            minutes: 5,
+            actors,
+        };
+
+        // WHEN
+        let bitmask: Vec<_> = (1..=3)
+            .map(|actors| graph.best_pressure(&scenario(actors), Solver::Bitmask))
+            .collect();
+        let branch_and_bound: Vec<_> = (1..=3)
+            .map(|actors| graph.best_pressure(&scenario(actors), Solver::BranchAndBound))
+            .collect();
+
+        // THEN
+        assert_eq!(vec![Ok(30), Ok(45), Ok(48)], bitmask);
+        assert_eq!(bitmask[..2], branch_and_bound[..2]);
+        assert!(branch_and_bound[2].is_err());
+    }
+
+    #[test]
+    fn graph_missing_start() {
+        // GIVEN
+        let input = indoc! {"
+            Valve BB has flow rate=13; tunnel leads to valve CC
+            Valve CC has flow rate=2; tunnel leads to valve BB
+            "};
+        let graph = Cave::parse(input).unwrap();
+
+        // WHEN
+        let result = graph.max_pressure();
+
+        // THEN
+        assert_eq!(
+            "invalid argument: there is no valve named `AA`",
+            result.err().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn graph_start_cannot_reach_every_valve() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnel leads to valve BB
+            Valve BB has flow rate=5; tunnel leads to valve AA
+            Valve CC has flow rate=10; tunnel leads to valve DD
+            Valve DD has flow rate=20; tunnel leads to valve CC
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let alone = [Solver::BranchAndBound, Solver::Bitmask].map(|s| cave.max_pressure_using(s));
+        let together = [Solver::BranchAndBound, Solver::Bitmask]
+            .map(|s| cave.max_pressure_with_elephant_using(s));
+        let plan = cave.plan().unwrap();
+
+        // THEN
+        assert_eq!([Ok(140), Ok(140)], alone);
+        assert_eq!([Ok(120), Ok(120)], together);
+        assert_eq!(140, plan.pressure());
     }
 }
@@ -0,0 +1,282 @@
+//! The valves opened by the best solution, and when, to check an answer by hand.
+
+use std::fmt::Display;
+
+use common::Result;
+
+use crate::{Cave, Scenario};
+
+/// A valve opened by an actor, after walking to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    pub valve: String,
+    /// Minute during which the valve is opened; it releases pressure from the next one
+    pub minute: usize,
+    /// Valves walked through since the previous opening, both ends included
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Actor {
+    pub name: String,
+    pub openings: Vec<Opening>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Minute {
+    pub minute: usize,
+    /// Valves releasing pressure during this minute
+    pub open: Vec<String>,
+    pub released: usize,
+    /// Pressure released since the start, this minute included
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub actors: Vec<Actor>,
+    pub timeline: Vec<Minute>,
+}
+
+impl Plan {
+    pub fn pressure(&self) -> usize {
+        self.timeline.last().map(|minute| minute.total).unwrap_or(0)
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for actor in &self.actors {
+            for opening in &actor.openings {
+                writeln!(
+                    f,
+                    "{}: {}, open {} at minute {}",
+                    actor.name,
+                    opening.path.join(" -> "),
+                    opening.valve,
+                    opening.minute
+                )?;
+            }
+        }
+        writeln!(f, "minute  released   total  open")?;
+        for minute in &self.timeline {
+            let line = format!(
+                "{:>6}  {:>8}  {:>6}  {}",
+                minute.minute,
+                minute.released,
+                minute.total,
+                minute.open.join(", ")
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+impl Cave {
+    /// How to release the most pressure in 30 minutes.
+    pub fn plan(&self) -> Result<Plan> {
+        self.plan_for(&Scenario::alone())
+    }
+
+    /// How to release the most pressure in 26 minutes, with the help of the elephant.
+    pub fn plan_with_elephant(&self) -> Result<Plan> {
+        self.plan_for(&Scenario::with_elephant())
+    }
+
+    /// How to release the most pressure in this scenario.
+    pub fn plan_for(&self, scenario: &Scenario) -> Result<Plan> {
+        let start = self.valve(&scenario.start)?;
+        let minutes = scenario.minutes;
+        let (_, masks) = self.best_masks(start, minutes, scenario.actors)?;
+        let valves = self.worth_opening();
+
+        let mut actors = Vec::new();
+        let mut opened = Vec::new();
+        for (i, mask) in masks.iter().enumerate() {
+            let mut chosen: Vec<usize> = (0..valves.len())
+                .filter(|bit| mask & (1 << bit) != 0)
+                .map(|bit| valves[bit])
+                .collect();
+            let (_, order) = self.best_order(start, minutes, &mut chosen);
+
+            let mut openings = Vec::new();
+            let mut current = start;
+            for (valve, remaining_minutes) in order {
+                let path = self
+                    .distances
+                    .path(current, valve)
+                    .expect("the chosen valves are reachable");
+                let minute = minutes - remaining_minutes;
+                openings.push(Opening {
+                    valve: self.names[valve].clone(),
+                    minute,
+                    path: path.into_iter().map(|v| self.names[v].clone()).collect(),
+                });
+                opened.push((minute, valve));
+                current = valve;
+            }
+            let name = match i {
+                0 => "you".to_string(),
+                1 => "elephant".to_string(),
+                _ => format!("actor {}", i + 1),
+            };
+            actors.push(Actor { name, openings });
+        }
+
+        opened.sort();
+        let mut total = 0;
+        let timeline = (1..=minutes)
+            .map(|minute| {
+                let open: Vec<usize> = opened
+                    .iter()
+                    .filter(|(opened_at, _)| *opened_at < minute)
+                    .map(|(_, valve)| *valve)
+                    .collect();
+                let released: usize = open.iter().map(|&valve| self.flow_rates[valve]).sum();
+                total += released;
+                Minute {
+                    minute,
+                    open: open
+                        .iter()
+                        .map(|&valve| self.names[valve].clone())
+                        .collect(),
+                    released,
+                    total,
+                }
+            })
+            .collect();
+
+        Ok(Plan { actors, timeline })
+    }
+
+    /// Best order in which to open all of `valves`, with the remaining minutes after
+    /// each opening.
+    fn best_order(
+        &self,
+        current_valve: usize,
+        remaining_minutes: usize,
+        valves: &mut Vec<usize>,
+    ) -> (usize, Vec<(usize, usize)>) {
+        let mut best = (0, Vec::new());
+        for i in 0..valves.len() {
+            let valve = valves[i];
+            let Some(distance) = self.distances.distance(current_valve, valve) else {
+                continue;
+            };
+            let cost = distance + 1;
+            if remaining_minutes <= cost {
+                continue;
+            }
+            let remaining_minutes = remaining_minutes - cost;
+            valves.swap_remove(i);
+            let (pressure, mut order) = self.best_order(valve, remaining_minutes, valves);
+            valves.push(valve);
+            let last = valves.len() - 1;
+            valves.swap(i, last);
+
+            let pressure = pressure + remaining_minutes * self.flow_rates[valve];
+            if pressure > best.0 {
+                order.insert(0, (valve, remaining_minutes));
+                best = (pressure, order);
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn plan_alone() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+            Valve BB has flow rate=13; tunnels lead to valves CC, AA
+            Valve CC has flow rate=2; tunnels lead to valves DD, BB
+            Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+            Valve EE has flow rate=3; tunnels lead to valves FF, DD
+            Valve FF has flow rate=0; tunnels lead to valves EE, GG
+            Valve GG has flow rate=0; tunnels lead to valves FF, HH
+            Valve HH has flow rate=22; tunnel leads to valve GG
+            Valve II has flow rate=0; tunnels lead to valves AA, JJ
+            Valve JJ has flow rate=21; tunnel leads to valve II
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let plan = cave.plan().unwrap();
+
+        // THEN
+        assert_eq!(1651, plan.pressure());
+        let openings: Vec<_> = plan.actors[0]
+            .openings
+            .iter()
+            .map(|o| (o.valve.as_str(), o.minute))
+            .collect();
+        assert_eq!(
+            vec![
+                ("DD", 2),
+                ("BB", 5),
+                ("JJ", 9),
+                ("HH", 17),
+                ("EE", 21),
+                ("CC", 24)
+            ],
+            openings
+        );
+        assert_eq!(
+            vec!["JJ", "II", "AA", "DD", "EE", "FF", "GG", "HH"],
+            plan.actors[0].openings[3].path
+        );
+        assert_eq!(
+            (20, 20),
+            (plan.timeline[2].released, plan.timeline[2].total)
+        );
+    }
+
+    #[test]
+    fn plan_with_elephant_display() {
+        // GIVEN
+        let input = indoc! {"
+            Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+            Valve BB has flow rate=13; tunnels lead to valves CC, AA
+            Valve CC has flow rate=2; tunnels lead to valves DD, BB
+            Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+            Valve EE has flow rate=3; tunnels lead to valves FF, DD
+            Valve FF has flow rate=0; tunnels lead to valves EE, GG
+            Valve GG has flow rate=0; tunnels lead to valves FF, HH
+            Valve HH has flow rate=22; tunnel leads to valve GG
+            Valve II has flow rate=0; tunnels lead to valves AA, JJ
+            Valve JJ has flow rate=21; tunnel leads to valve II
+            "};
+        let cave = Cave::parse(input).unwrap();
+
+        // WHEN
+        let plan = cave.plan_with_elephant().unwrap();
+
+        // THEN
+        assert_eq!(1707, plan.pressure());
+        let text = plan.to_string();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(
+            vec![
+                "you: AA -> II -> JJ, open JJ at minute 3",
+                "you: JJ -> II -> AA -> BB, open BB at minute 7",
+                "you: BB -> CC, open CC at minute 9",
+                "elephant: AA -> DD, open DD at minute 2",
+                "elephant: DD -> EE -> FF -> GG -> HH, open HH at minute 7",
+                "elephant: HH -> GG -> FF -> EE, open EE at minute 11",
+                "minute  released   total  open",
+            ],
+            lines[..7]
+        );
+        assert_eq!(
+            "    26        81    1707  DD, JJ, BB, HH, CC, EE",
+            lines[32]
+        );
+    }
+}
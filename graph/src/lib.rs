@@ -0,0 +1,49 @@
+//! Weighted directed graphs over nodes numbered from 0, and their shortest paths.
+
+mod list;
+mod matrix;
+
+pub use list::AdjacencyList;
+pub use matrix::{AllPairs, Matrix};
+
+/// Shortest paths from a single node to every other one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    source: usize,
+    distances: Vec<Option<usize>>,
+    previous: Vec<Option<usize>>,
+}
+
+impl Paths {
+    fn new(source: usize, nodes: usize) -> Self {
+        let mut distances = vec![None; nodes];
+        distances[source] = Some(0);
+        Paths {
+            source,
+            distances,
+            previous: vec![None; nodes],
+        }
+    }
+
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Length of the shortest path to `node`, if it can be reached.
+    pub fn distance(&self, node: usize) -> Option<usize> {
+        self.distances[node]
+    }
+
+    /// Nodes along the shortest path to `node`, both ends included.
+    pub fn path_to(&self, node: usize) -> Option<Vec<usize>> {
+        self.distances[node]?;
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(previous) = self.previous[current] {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
@@ -0,0 +1,225 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::{Matrix, Paths};
+
+/// Outgoing edges of each node, with their weights.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdjacencyList {
+    edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl AdjacencyList {
+    pub fn new(nodes: usize) -> Self {
+        AdjacencyList {
+            edges: vec![Vec::new(); nodes],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: usize) {
+        self.edges[from].push((to, weight));
+    }
+
+    /// Nodes reachable from `node` in one step, and the weight of each edge.
+    pub fn neighbors(&self, node: usize) -> &[(usize, usize)] {
+        &self.edges[node]
+    }
+
+    /// Shortest paths counting every edge as one step, whatever its weight.
+    pub fn bfs(&self, source: usize) -> Paths {
+        let mut paths = Paths::new(source, self.len());
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            let steps = paths.distances[node].unwrap() + 1;
+            for &(next, _) in &self.edges[node] {
+                if paths.distances[next].is_none() {
+                    paths.distances[next] = Some(steps);
+                    paths.previous[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        paths
+    }
+
+    pub fn dijkstra(&self, source: usize) -> Paths {
+        let mut paths = Paths::new(source, self.len());
+        let mut queue = BinaryHeap::from([Reverse((0, source))]);
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if paths.distances[node].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for &(next, weight) in &self.edges[node] {
+                let candidate = distance + weight;
+                if paths.distances[next].is_none_or(|best| candidate < best) {
+                    paths.distances[next] = Some(candidate);
+                    paths.previous[next] = Some(node);
+                    queue.push(Reverse((candidate, next)));
+                }
+            }
+        }
+        paths
+    }
+
+    /// Shortest path from `source` to `goal` and its length, guided by a
+    /// `heuristic` that never overestimates the distance left to the goal.
+    pub fn a_star(
+        &self,
+        source: usize,
+        goal: usize,
+        heuristic: impl Fn(usize) -> usize,
+    ) -> Option<(usize, Vec<usize>)> {
+        let mut paths = Paths::new(source, self.len());
+        let mut queue = BinaryHeap::from([Reverse((heuristic(source), 0, source))]);
+        while let Some(Reverse((_, distance, node))) = queue.pop() {
+            if node == goal {
+                return Some((distance, paths.path_to(goal).unwrap()));
+            }
+            if paths.distances[node].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for &(next, weight) in &self.edges[node] {
+                let candidate = distance + weight;
+                if paths.distances[next].is_none_or(|best| candidate < best) {
+                    paths.distances[next] = Some(candidate);
+                    paths.previous[next] = Some(node);
+                    queue.push(Reverse((candidate + heuristic(next), candidate, next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Weights of the edges, keeping the lightest one between two nodes.
+    pub fn to_matrix(&self) -> Matrix {
+        let mut matrix = Matrix::new(self.len());
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, weight) in edges {
+                matrix[(from, to)] = matrix[(from, to)].min(weight);
+            }
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_bfs() {
+        // GIVEN 0 -> 1 -> 3 is shorter in steps, 0 -> 2 -> 3 in weight; 4 is unreachable
+        let mut graph = AdjacencyList::new(5);
+        for (from, to, weight) in [
+            (0, 1, 10),
+            (1, 3, 10),
+            (0, 2, 1),
+            (2, 1, 1),
+            (2, 3, 5),
+            (4, 0, 1),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+
+        // WHEN
+        let paths = graph.bfs(0);
+
+        // THEN
+        assert_eq!(
+            vec![Some(0), Some(1), Some(1), Some(2), None],
+            (0..5).map(|node| paths.distance(node)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(vec![0, 1, 3]), paths.path_to(3));
+        assert_eq!(None, paths.path_to(4));
+    }
+
+    #[test]
+    fn list_dijkstra() {
+        // GIVEN 0 -> 1 -> 3 is shorter in steps, 0 -> 2 -> 3 in weight; 4 is unreachable
+        let mut graph = AdjacencyList::new(5);
+        for (from, to, weight) in [
+            (0, 1, 10),
+            (1, 3, 10),
+            (0, 2, 1),
+            (2, 1, 1),
+            (2, 3, 5),
+            (4, 0, 1),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+
+        // WHEN
+        let paths = graph.dijkstra(0);
+
+        // THEN
+        assert_eq!(
+            vec![Some(0), Some(2), Some(1), Some(6), None],
+            (0..5).map(|node| paths.distance(node)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(vec![0, 2, 3]), paths.path_to(3));
+        assert_eq!(Some(vec![0, 2, 1]), paths.path_to(1));
+    }
+
+    #[test]
+    fn list_a_star_on_grid() {
+        // GIVEN a 4x3 grid without the cells 1, 5 and 6
+        //   . # . .
+        //   . # # .
+        //   . . . .
+        let (width, walls) = (4, [1, 5, 6]);
+        let mut graph = AdjacencyList::new(12);
+        for node in (0..12).filter(|node| !walls.contains(node)) {
+            let (x, y) = (node % width, node / width);
+            let mut link = |next: usize| {
+                if !walls.contains(&next) {
+                    graph.add_edge(node, next, 1);
+                }
+            };
+            if x > 0 {
+                link(node - 1);
+            }
+            if x < width - 1 {
+                link(node + 1);
+            }
+            if y > 0 {
+                link(node - width);
+            }
+            if y < 2 {
+                link(node + width);
+            }
+        }
+        let goal = 2;
+        let manhattan = |node: usize| (node % width).abs_diff(2) + node / width;
+
+        // WHEN
+        let result = graph.a_star(0, goal, manhattan);
+
+        // THEN
+        assert_eq!(Some((8, vec![0, 4, 8, 9, 10, 11, 7, 3, 2])), result);
+        assert_eq!(graph.dijkstra(0).distance(goal), result.map(|r| r.0));
+        assert_eq!(None, graph.a_star(0, 1, manhattan));
+    }
+
+    #[test]
+    fn list_to_matrix() {
+        // GIVEN two edges from 0 to 1, and none back
+        let mut graph = AdjacencyList::new(2);
+        graph.add_edge(0, 1, 10);
+        graph.add_edge(0, 1, 3);
+
+        // WHEN
+        let matrix = graph.to_matrix();
+
+        // THEN
+        assert_eq!(3, matrix[(0, 1)]);
+        assert_eq!(Matrix::INFINITY, matrix[(1, 0)]);
+    }
+}
@@ -0,0 +1,166 @@
+use std::ops::{Index, IndexMut};
+
+/// Weights of the edges between every pair of nodes, indexed by `(from, to)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    width: usize,
+    cells: Vec<usize>,
+}
+
+impl Matrix {
+    /// Weight between two nodes without an edge.
+    pub const INFINITY: usize = usize::MAX;
+
+    pub fn new(width: usize) -> Self {
+        Matrix {
+            width,
+            cells: vec![Matrix::INFINITY; width * width],
+        }
+    }
+
+    /// Matrix of `width` nodes from its rows, laid out one after the other.
+    pub fn from_cells(width: usize, cells: Vec<usize>) -> Self {
+        assert_eq!(width * width, cells.len());
+        Matrix { width, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Shortest distances between every pair of nodes.
+    pub fn floyd_warshall(&self) -> AllPairs {
+        let mut distances = self.clone();
+        let mut next = vec![None; self.width * self.width];
+        for i in 0..self.width {
+            for j in 0..self.width {
+                if distances[(i, j)] != Matrix::INFINITY {
+                    next[i * self.width + j] = Some(j);
+                }
+            }
+            distances[(i, i)] = 0;
+            next[i * self.width + i] = Some(i);
+        }
+
+        for k in 0..self.width {
+            for i in 0..self.width {
+                for j in 0..self.width {
+                    let through_k = distances[(i, k)].saturating_add(distances[(k, j)]);
+                    if through_k < distances[(i, j)] {
+                        distances[(i, j)] = through_k;
+                        next[i * self.width + j] = next[i * self.width + k];
+                    }
+                }
+            }
+        }
+        AllPairs { distances, next }
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = usize;
+
+    fn index(&self, (from, to): (usize, usize)) -> &Self::Output {
+        &self.cells[from * self.width + to]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (from, to): (usize, usize)) -> &mut Self::Output {
+        &mut self.cells[from * self.width + to]
+    }
+}
+
+/// Shortest paths between every pair of nodes, indexed by `(from, to)` like the
+/// matrix of their distances.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairs {
+    distances: Matrix,
+    /// Node following `from` on the way to `to`
+    next: Vec<Option<usize>>,
+}
+
+impl AllPairs {
+    pub fn distances(&self) -> &Matrix {
+        &self.distances
+    }
+
+    pub fn distance(&self, from: usize, to: usize) -> Option<usize> {
+        Some(self.distances[(from, to)]).filter(|&d| d != Matrix::INFINITY)
+    }
+
+    /// Nodes along the shortest path from `from` to `to`, both ends included.
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let width = self.distances.width;
+        let mut path = vec![from];
+        let mut current = from;
+        while current != to {
+            current = self.next[current * width + to]?;
+            path.push(current);
+        }
+        Some(path)
+    }
+}
+
+impl Index<(usize, usize)> for AllPairs {
+    type Output = usize;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.distances[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdjacencyList;
+
+    #[test]
+    fn matrix_floyd_warshall() {
+        // GIVEN a square 0 - 1 - 2 - 3 - 0 with a heavy diagonal 0 - 2, and 4 alone
+        let mut graph = AdjacencyList::new(5);
+        for (a, b, weight) in [(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1), (0, 2, 5)] {
+            graph.add_edge(a, b, weight);
+            graph.add_edge(b, a, weight);
+        }
+
+        // WHEN
+        let all_pairs = graph.to_matrix().floyd_warshall();
+
+        // THEN
+        #[rustfmt::skip]
+        let expected = Matrix::from_cells(5, vec![
+            0, 1, 2, 1, usize::MAX,
+            1, 0, 1, 2, usize::MAX,
+            2, 1, 0, 1, usize::MAX,
+            1, 2, 1, 0, usize::MAX,
+            usize::MAX, usize::MAX, usize::MAX, usize::MAX, 0,
+        ]);
+        assert_eq!(&expected, all_pairs.distances());
+        assert_eq!(Some(vec![0, 1, 2]), all_pairs.path(0, 2));
+        assert_eq!(Some(vec![3]), all_pairs.path(3, 3));
+        assert_eq!(None, all_pairs.path(0, 4));
+        assert_eq!(None, all_pairs.distance(4, 0));
+    }
+
+    #[test]
+    fn matrix_floyd_warshall_matches_dijkstra() {
+        // GIVEN
+        let mut graph = AdjacencyList::new(4);
+        for (a, b, weight) in [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 7)] {
+            graph.add_edge(a, b, weight);
+        }
+
+        // WHEN
+        let all_pairs = graph.to_matrix().floyd_warshall();
+
+        // THEN
+        for from in 0..4 {
+            let paths = graph.dijkstra(from);
+            for to in 0..4 {
+                assert_eq!(paths.distance(to), all_pairs.distance(from, to));
+            }
+        }
+        assert_eq!(Some(vec![0, 2, 1, 3]), all_pairs.path(0, 3));
+    }
+}